@@ -0,0 +1,42 @@
+//! Benchmarks every registered puzzle on its `actual` input from answers.toml, the puzzles
+//! without one are skipped. The benchmarks are named `<year>/<day>/part<part>`, so they are
+//! selected with the filter of criterion:
+//!
+//! ```sh
+//! cargo bench -- 2022/
+//! cargo bench -- 2022/09/
+//! cargo bench -- 2022/09/part2
+//! ```
+use advent::answers::{self, AnswerDb};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use std::fs;
+
+pub fn criterion_benchmark(c: &mut Criterion) {
+    let content = fs::read_to_string(answers::DEFAULT_PATH).unwrap();
+    let db = AnswerDb::parse(&content).unwrap();
+    for solution in advent::registry().iter() {
+        let (year, day) = (solution.year(), solution.day());
+        let Some(entry) = db.entry(year, day, "actual") else {
+            continue;
+        };
+        let file_content = fs::read_to_string(&entry.input).unwrap();
+        let mut params = solution.params();
+        for (name, value) in &entry.params {
+            params.set(name, value).unwrap();
+        }
+
+        let settings = solution.bench_settings();
+        let mut group = c.benchmark_group(format!("{year}/{day:02}"));
+        group.sample_size(settings.sample_size);
+        group.measurement_time(settings.measurement_time);
+        for part in [1, 2] {
+            group.bench_function(format!("part{part}"), |b| {
+                b.iter(|| solution.solve(part, black_box(&file_content), &params))
+            });
+        }
+        group.finish();
+    }
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
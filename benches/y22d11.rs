@@ -1,11 +1,25 @@
-use advent::y22d11::{solve_task1, solve_task2};
+//! Compares the item by item simulation of 2022 day 11 with the round by round one it
+//! replaced, on the actual input:
+//!
+//! ```sh
+//! cargo bench --bench y22d11
+//! ```
+use advent::y22d11;
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
 use std::fs;
 
 pub fn criterion_benchmark(c: &mut Criterion) {
-    let content = fs::read_to_string("./benches/y22d11.txt").unwrap();
-    c.bench_function("solve 1", |b| b.iter(|| solve_task1(black_box(&content))));
-    c.bench_function("solve 2", |b| b.iter(|| solve_task2(black_box(&content))));
+    let file_content = fs::read_to_string("benches/y22d11.txt").unwrap();
+    for (part, rounds, divider) in [(1, 20, 3), (2, 10_000, 1)] {
+        let mut group = c.benchmark_group(format!("2022/11/part{part}"));
+        group.bench_function("rounds", |b| {
+            b.iter(|| y22d11::rounds::solve(black_box(&file_content), rounds, divider))
+        });
+        group.bench_function("items", |b| {
+            b.iter(|| y22d11::solve(black_box(&file_content), rounds, divider))
+        });
+        group.finish();
+    }
 }
 
 criterion_group!(benches, criterion_benchmark);
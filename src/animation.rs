@@ -0,0 +1,207 @@
+//! Step by step views of the simulations. A [`Frames`] producer draws its current state as an
+//! [`Image`], the frames are played in the terminal with ANSI colors or saved as numbered PPM
+//! images.
+//!
+//! ```
+//! use advent::animation::{sample, Frames};
+//! use advent::grid::Grid;
+//! use advent::render::Image;
+//!
+//! /// A pixel crossing the row from the left to the right.
+//! struct Walk(usize);
+//!
+//! impl Frames for Walk {
+//!     fn frame(&self) -> Image {
+//!         let mut pixels = Grid::new(1, 4, 0);
+//!         pixels[(0, self.0)] = 1;
+//!         Image::new(pixels)
+//!     }
+//!
+//!     fn step(&mut self) -> bool {
+//!         if self.0 == 3 {
+//!             return false;
+//!         }
+//!         self.0 += 1;
+//!         true
+//!     }
+//! }
+//!
+//! // the first frame, every second step and the last one
+//! assert_eq!(sample(&mut Walk(0), 2).count(), 3);
+//! ```
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::Path,
+    thread,
+    time::Duration,
+};
+
+use crate::render::{Image, Rgb};
+
+/// Simulation that can be watched one step at a time.
+pub trait Frames {
+    /// Draws the current state.
+    fn frame(&self) -> Image;
+
+    /// Advances the simulation by one step, returns `false` once it has finished and the
+    /// state no longer changes.
+    fn step(&mut self) -> bool;
+}
+
+/// Iterator over the frames of every `every` steps, see [`sample`].
+pub struct Sample<'a> {
+    frames: &'a mut dyn Frames,
+    every: usize,
+    started: bool,
+    finished: bool,
+}
+
+/// Returns the first frame, the frame after every `every` steps and the last one.
+pub fn sample(frames: &mut dyn Frames, every: usize) -> Sample<'_> {
+    Sample {
+        frames,
+        every: every.max(1),
+        started: false,
+        finished: false,
+    }
+}
+
+impl Iterator for Sample<'_> {
+    type Item = Image;
+
+    fn next(&mut self) -> Option<Image> {
+        if !self.started {
+            self.started = true;
+            return Some(self.frames.frame());
+        }
+        if self.finished {
+            return None;
+        }
+        for steps in 0..self.every {
+            if !self.frames.step() {
+                self.finished = true;
+                // the last state was shown already unless some steps were made since
+                return (steps > 0).then(|| self.frames.frame());
+            }
+        }
+        Some(self.frames.frame())
+    }
+}
+
+/// Plays the frames in the terminal, waiting `delay` after each of them. Every character
+/// shows two pixels one above the other. Returns the number of the frames.
+pub fn play(
+    images: impl IntoIterator<Item = Image>,
+    out: &mut impl Write,
+    delay: Duration,
+) -> io::Result<usize> {
+    // clear the screen and hide the cursor
+    write!(out, "\x1b[2J\x1b[?25l")?;
+    let mut count = 0;
+    for image in images {
+        write!(out, "\x1b[H{}", to_ansi(&image))?;
+        out.flush()?;
+        count += 1;
+        thread::sleep(delay);
+    }
+    write!(out, "\x1b[?25h")?;
+    out.flush()?;
+    Ok(count)
+}
+
+/// Draws the image with the upper half block characters in 24-bit colors.
+pub fn to_ansi(image: &Image) -> String {
+    let pixels = image.pixels();
+    let mut res = String::new();
+    for row in (0..image.height()).step_by(2) {
+        let mut colors: Option<(Rgb, Option<Rgb>)> = None;
+        for col in 0..image.width() {
+            let upper = image.color(pixels[(row, col)]);
+            let lower = pixels.get((row + 1, col)).map(|&index| image.color(index));
+            if colors != Some((upper, lower)) {
+                let [r, g, b] = upper;
+                res += &format!("\x1b[38;2;{r};{g};{b}m");
+                match lower {
+                    Some([r, g, b]) => res += &format!("\x1b[48;2;{r};{g};{b}m"),
+                    None => res += "\x1b[49m",
+                }
+                colors = Some((upper, lower));
+            }
+            res.push('▀');
+        }
+        res += "\x1b[0m\n";
+    }
+    res
+}
+
+/// Saves the frames as `frame00000.ppm`, `frame00001.ppm` and so on in the directory, which
+/// is created if needed. Returns the number of the frames.
+pub fn write_frames(
+    images: impl IntoIterator<Item = Image>,
+    dir: &Path,
+    scale: usize,
+) -> io::Result<usize> {
+    std::fs::create_dir_all(dir)?;
+    let mut count = 0;
+    for image in images {
+        let mut out = BufWriter::new(File::create(dir.join(format!("frame{count:05}.ppm")))?);
+        image.write_ppm(&mut out, scale)?;
+        out.flush()?;
+        count += 1;
+    }
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::Grid;
+
+    /// Counts the steps up to the limit, the frame shows the count as a one pixel image.
+    struct Counter {
+        count: u8,
+        limit: u8,
+    }
+
+    impl Frames for Counter {
+        fn frame(&self) -> Image {
+            Image::new(Grid::new(1, 1, self.count))
+        }
+
+        fn step(&mut self) -> bool {
+            if self.count == self.limit {
+                return false;
+            }
+            self.count += 1;
+            true
+        }
+    }
+
+    fn sampled(limit: u8, every: usize) -> Vec<u8> {
+        let mut counter = Counter { count: 0, limit };
+        sample(&mut counter, every)
+            .map(|image| image.pixels()[(0, 0)])
+            .collect()
+    }
+
+    #[test]
+    fn test_sample() {
+        assert_eq!(sampled(0, 1), vec![0]);
+        assert_eq!(sampled(3, 1), vec![0, 1, 2, 3]);
+        assert_eq!(sampled(4, 2), vec![0, 2, 4]);
+        assert_eq!(sampled(5, 2), vec![0, 2, 4, 5]);
+        assert_eq!(sampled(5, 0), vec![0, 1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn test_ansi() {
+        let image = Image::from_text("#.\n.#\n##", |c| u8::from(c == '#'));
+        let black_on_white = "\x1b[38;2;0;0;0m\x1b[48;2;255;255;255m▀";
+        let white_on_black = "\x1b[38;2;255;255;255m\x1b[48;2;0;0;0m▀";
+        assert_eq!(
+            to_ansi(&image),
+            format!("{black_on_white}{white_on_black}\x1b[0m\n\x1b[38;2;0;0;0m\x1b[49m▀▀\x1b[0m\n")
+        );
+    }
+}
@@ -0,0 +1,339 @@
+//! Known answers of the puzzles. They are stored in `answers.toml`, one table per input:
+//!
+//! ```toml
+//! [2022.13.actual]
+//! input = "benches/y22d13.txt"
+//! part1 = 5003
+//! part2 = 20280
+//!
+//! [2022.15.example]
+//! input = "src/y22d15/example.txt"
+//! params = { row = 10, max = 20 }
+//! part1 = 26
+//! ```
+use crate::solution::Answer;
+use toml_edit::{value, Document, Item, Table, Value};
+
+pub const DEFAULT_PATH: &str = "answers.toml";
+
+/// An input of a puzzle together with the answers known for it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    pub year: u16,
+    pub day: u8,
+    pub name: String,
+    /// Path of the input file relative to the repository root.
+    pub input: String,
+    /// Parameters of the solution that differ from the defaults for this input.
+    pub params: Vec<(String, String)>,
+    pub answers: [Option<String>; 2],
+}
+
+impl Entry {
+    /// Returns the known answer of the part (counting from 1).
+    pub fn answer(&self, part: u8) -> Option<&str> {
+        self.answers
+            .get(usize::from(part).checked_sub(1)?)?
+            .as_deref()
+    }
+}
+
+/// Checks whether `answer` is the same as the `expected` one read from the database.
+pub fn is_same(expected: &str, answer: &Answer) -> bool {
+    expected.trim_end() == answer.to_string().trim_end()
+}
+
+#[derive(Debug, Clone)]
+pub struct AnswerDb {
+    document: Document,
+    entries: Vec<Entry>,
+}
+
+impl AnswerDb {
+    pub fn parse(content: &str) -> Result<Self, String> {
+        let document = content.parse::<Document>().map_err(|e| e.to_string())?;
+        let mut entries = Vec::new();
+        for (year_key, year_item) in document.iter() {
+            let year = parse_key::<u16>(year_key, "year")?;
+            for (day_key, day_item) in tables(year_key, year_item)? {
+                let day = parse_key::<u8>(day_key, "day")?;
+                let path = format!("{year_key}.{day_key}");
+                for (name, item) in tables(&path, day_item)? {
+                    let path = format!("{path}.{name}");
+                    let table = item
+                        .as_table_like()
+                        .ok_or_else(|| format!("{path}: expected a table"))?;
+                    let input = table
+                        .get("input")
+                        .and_then(Item::as_str)
+                        .ok_or_else(|| format!("{path}: 'input' is missing"))?;
+                    let params = match table.get("params") {
+                        None => Vec::new(),
+                        Some(item) => item
+                            .as_table_like()
+                            .ok_or_else(|| format!("{path}.params: expected a table"))?
+                            .iter()
+                            .map(|(name, value)| {
+                                answer_text(value)
+                                    .map(|value| (name.to_owned(), value))
+                                    .ok_or_else(|| {
+                                        format!(
+                                            "{path}.params.{name}: expected a string or an integer"
+                                        )
+                                    })
+                            })
+                            .collect::<Result<_, _>>()?,
+                    };
+                    let answer = |key| match table.get(key) {
+                        None => Ok(None),
+                        Some(item) => answer_text(item).map(Some).ok_or_else(|| {
+                            format!("{path}.{key}: expected a string or an integer")
+                        }),
+                    };
+                    entries.push(Entry {
+                        year,
+                        day,
+                        name: name.to_owned(),
+                        input: input.to_owned(),
+                        params,
+                        answers: [answer("part1")?, answer("part2")?],
+                    });
+                }
+            }
+        }
+        Ok(Self { document, entries })
+    }
+
+    pub fn entries(&self) -> &[Entry] {
+        &self.entries
+    }
+
+    /// Returns the entries of the puzzle in the order they are written in the file.
+    pub fn puzzle_entries(&self, year: u16, day: u8) -> impl Iterator<Item = &Entry> + '_ {
+        self.entries
+            .iter()
+            .filter(move |e| e.year == year && e.day == day)
+    }
+
+    pub fn entry(&self, year: u16, day: u8, name: &str) -> Option<&Entry> {
+        self.puzzle_entries(year, day).find(|e| e.name == name)
+    }
+
+    /// Stores the answer of the part for an existing entry, keeping the rest of the file intact.
+    pub fn record(&mut self, year: u16, day: u8, name: &str, part: u8, answer: &Answer) {
+        let Some(entry) = self
+            .entries
+            .iter_mut()
+            .find(|e| e.year == year && e.day == day && e.name == name)
+        else {
+            panic!("{year} day {day} has no '{name}' input");
+        };
+        let key = format!("part{part}");
+        self.document[&year.to_string()][&day.to_string()][name][&key] =
+            Item::Value(answer_value(answer));
+        entry.answers[usize::from(part) - 1] = Some(answer.to_string());
+    }
+
+    /// Adds an input without answers, returns `false` if the puzzle already has the input.
+    pub fn add_input(&mut self, year: u16, day: u8, name: &str, input: &str) -> bool {
+        if self.entry(year, day, name).is_some() {
+            return false;
+        }
+        let year_table = implicit_table(self.document.as_table_mut(), &year.to_string());
+        let day_table = implicit_table(year_table, &day.to_string());
+        let mut table = Table::new();
+        table.decor_mut().set_prefix("\n");
+        table.insert("input", value(input));
+        day_table.insert(name, Item::Table(table));
+        self.entries.push(Entry {
+            year,
+            day,
+            name: name.to_owned(),
+            input: input.to_owned(),
+            params: Vec::new(),
+            answers: [None, None],
+        });
+        true
+    }
+}
+
+/// Returns the table under the key, creating one that has no header of its own if it is missing.
+fn implicit_table<'a>(table: &'a mut Table, key: &str) -> &'a mut Table {
+    let item = table.entry(key).or_insert_with(|| {
+        let mut table = Table::new();
+        table.set_implicit(true);
+        Item::Table(table)
+    });
+    item.as_table_mut()
+        .unwrap_or_else(|| panic!("'{key}' is expected to be a table"))
+}
+
+impl std::fmt::Display for AnswerDb {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.document)
+    }
+}
+
+fn parse_key<T: std::str::FromStr>(key: &str, name: &str) -> Result<T, String> {
+    key.parse()
+        .map_err(|_| format!("'{key}' is not a valid {name}"))
+}
+
+fn tables<'a>(
+    path: &str,
+    item: &'a Item,
+) -> Result<impl Iterator<Item = (&'a str, &'a Item)> + 'a, String> {
+    item.as_table_like()
+        .map(|table| table.iter())
+        .ok_or_else(|| format!("{path}: expected a table"))
+}
+
+fn answer_text(item: &Item) -> Option<String> {
+    match item.as_value()? {
+        Value::String(text) => Some(text.value().to_owned()),
+        Value::Integer(integer) => Some(integer.value().to_string()),
+        _ => None,
+    }
+}
+
+fn answer_value(answer: &Answer) -> Value {
+    match answer {
+        Answer::Integer(integer) => match i64::try_from(*integer) {
+            Ok(integer) => integer.into(),
+            Err(_) => integer.to_string().into(),
+        },
+        Answer::Text(text) => text.as_str().into(),
+        Answer::Art(art) => {
+            let art = art.trim_end();
+            format!("'''\n{art}\n'''")
+                .parse()
+                .unwrap_or_else(|_| art.into())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONTENT: &str = "# answers
+[2022.13.example]
+input = \"src/y22d13/example.txt\"
+part1 = 13
+part2 = \"140\"
+
+[2022.13.actual]
+input = \"benches/y22d13.txt\"
+part1 = 5003
+
+[2022.15.example]
+input = \"src/y22d15/example.txt\"
+params = { row = 10, max = \"20\" }
+";
+
+    #[test]
+    fn test_parse() {
+        let db = AnswerDb::parse(CONTENT).unwrap();
+        let entries = db.puzzle_entries(2022, 13).collect::<Vec<_>>();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].name, "example");
+        assert_eq!(entries[0].answer(1), Some("13"));
+        assert_eq!(entries[0].answer(2), Some("140"));
+        assert_eq!(entries[1].input, "benches/y22d13.txt");
+        assert_eq!(entries[1].answer(2), None);
+        assert_eq!(db.puzzle_entries(2022, 14).count(), 0);
+        let entry = db.puzzle_entries(2022, 15).next().unwrap();
+        assert_eq!(
+            entry.params,
+            vec![
+                ("row".to_owned(), "10".to_owned()),
+                ("max".to_owned(), "20".to_owned())
+            ]
+        );
+        assert_eq!(entries[0].params, vec![]);
+    }
+
+    #[test]
+    fn test_invalid() {
+        assert_eq!(
+            AnswerDb::parse("[2022.13.actual]\npart1 = 1\n").unwrap_err(),
+            "2022.13.actual: 'input' is missing"
+        );
+        assert_eq!(
+            AnswerDb::parse("[2022.x.actual]\ninput = \"x\"\n").unwrap_err(),
+            "'x' is not a valid day"
+        );
+    }
+
+    #[test]
+    fn test_record() {
+        let mut db = AnswerDb::parse(CONTENT).unwrap();
+        db.record(2022, 13, "actual", 2, &Answer::from(20280));
+        db.record(2022, 13, "example", 1, &Answer::from("#.\n.#\n"));
+        assert_eq!(
+            db.to_string(),
+            "# answers
+[2022.13.example]
+input = \"src/y22d13/example.txt\"
+part1 = '''
+#.
+.#
+'''
+part2 = \"140\"
+
+[2022.13.actual]
+input = \"benches/y22d13.txt\"
+part1 = 5003
+part2 = 20280
+
+[2022.15.example]
+input = \"src/y22d15/example.txt\"
+params = { row = 10, max = \"20\" }
+"
+        );
+        let db = AnswerDb::parse(&db.to_string()).unwrap();
+        assert_eq!(db.entries()[0].answer(1), Some("#.\n.#\n"));
+    }
+
+    #[test]
+    fn test_add_input() {
+        let mut db = AnswerDb::parse("[2022.13.actual]\ninput = \"a.txt\"\n").unwrap();
+        assert!(!db.add_input(2022, 13, "actual", "b.txt"));
+        assert!(db.add_input(2022, 14, "actual", "c.txt"));
+        assert!(db.add_input(2023, 1, "example", "d.txt"));
+        assert_eq!(
+            db.to_string(),
+            "[2022.13.actual]\ninput = \"a.txt\"\n\n[2022.14.actual]\ninput = \"c.txt\"\n\n[2023.1.example]\ninput = \"d.txt\"\n"
+        );
+        let db = AnswerDb::parse(&db.to_string()).unwrap();
+        assert_eq!(db.puzzle_entries(2023, 1).count(), 1);
+    }
+
+    #[test]
+    fn test_answers_file() {
+        let content = std::fs::read_to_string(DEFAULT_PATH).unwrap();
+        let db = AnswerDb::parse(&content).unwrap();
+        let registry = crate::registry();
+        for entry in db.entries() {
+            let solution = registry
+                .get(entry.year, entry.day)
+                .unwrap_or_else(|| panic!("{} day {} is not registered", entry.year, entry.day));
+            let mut params = solution.params();
+            for (name, value) in &entry.params {
+                params.set(name, value).unwrap();
+            }
+            assert!(
+                std::path::Path::new(&entry.input).exists(),
+                "{} does not exist",
+                entry.input
+            );
+        }
+    }
+
+    #[test]
+    fn test_is_same() {
+        assert!(is_same("5003", &Answer::from(5003)));
+        assert!(is_same("#.\n.#\n", &Answer::from("#.\n.#")));
+        assert!(!is_same("5003", &Answer::from(5004)));
+    }
+}
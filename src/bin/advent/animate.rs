@@ -0,0 +1,104 @@
+use crate::{
+    args::{parse_value, parse_year, read_input},
+    render::parse_palette,
+};
+use advent::animation::{play, sample, write_frames};
+use std::{io, path::Path, time::Duration};
+
+/// The number of the frames saved with --output unless --frames says otherwise, the
+/// simulations can make a frame per step of millions of them.
+const OUTPUT_FRAMES: usize = 1000;
+
+pub fn animate(mut args: impl Iterator<Item = String>) -> Result<(), String> {
+    let mut positional = Vec::new();
+    let mut assignments = Vec::new();
+    let mut fps = 30.0;
+    let mut every = 1;
+    let mut limit = None;
+    let mut scale = 1;
+    let mut palette = None;
+    let mut output = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--param" | "-P" => {
+                assignments.push(args.next().ok_or("--param requires name=value")?);
+            }
+            "--fps" => {
+                let value = args.next().ok_or("--fps requires a value")?;
+                fps = parse_value("fps", &value)?;
+                if !(fps > 0.0 && f64::is_finite(fps)) {
+                    return Err(format!("invalid fps: '{value}'"));
+                }
+            }
+            "--every" => {
+                let value = args.next().ok_or("--every requires a value")?;
+                every = parse_value("every", &value)?;
+            }
+            "--frames" => {
+                let value = args.next().ok_or("--frames requires a value")?;
+                limit = Some(parse_value("frames", &value)?);
+            }
+            "--scale" => {
+                let value = args.next().ok_or("--scale requires a value")?;
+                scale = parse_value("scale", &value)?;
+                if scale == 0 {
+                    return Err("invalid scale: '0'".to_owned());
+                }
+            }
+            "--palette" => {
+                let value = args.next().ok_or("--palette requires colors")?;
+                palette = Some(parse_palette(&value)?);
+            }
+            "--output" | "-o" => output = Some(args.next().ok_or("--output requires a directory")?),
+            _ => positional.push(arg),
+        }
+    }
+
+    let (year, day, input) = match positional.as_slice() {
+        [year, day] => (year, day, None),
+        [year, day, input] => (year, day, Some(input.as_str())),
+        _ => return Err("expected <year> <day> [input]".to_owned()),
+    };
+    let year = parse_year(year)?;
+    let day: u8 = parse_value("day", day)?;
+    let solution = advent::registry()
+        .get(year, day)
+        .ok_or_else(|| format!("{year} day {day} is not solved yet"))?;
+
+    let mut params = solution.params();
+    for assignment in &assignments {
+        params
+            .assign(assignment)
+            .map_err(|e| format!("{year} day {day}: {e}"))?;
+    }
+
+    let file_content = read_input(input)?;
+    let mut frames = solution
+        .animate(&file_content, &params)
+        .ok_or_else(|| format!("{year} day {day} has no animation"))?
+        .map_err(|e| e.to_string())?;
+    let limit = match output {
+        Some(_) => limit.unwrap_or(OUTPUT_FRAMES),
+        None => limit.unwrap_or(usize::MAX),
+    };
+    let images = sample(frames.as_mut(), every)
+        .take(limit)
+        .map(|image| match &palette {
+            Some(palette) => image.with_palette(palette.clone()),
+            None => image,
+        });
+
+    match output {
+        Some(dir) => {
+            let count = write_frames(images, Path::new(&dir), scale)
+                .map_err(|e| format!("cannot write the frames to {dir}: {e}"))?;
+            println!("{year} day {day}: {count} frames in {dir}");
+        }
+        None => {
+            let delay = Duration::from_secs_f64(1.0 / fps);
+            play(images, &mut io::stdout().lock(), delay)
+                .map_err(|e| format!("cannot play the animation: {e}"))?;
+        }
+    }
+    Ok(())
+}
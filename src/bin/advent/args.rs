@@ -0,0 +1,45 @@
+use std::{fs, io::Read, str::FromStr, time::Duration};
+
+pub fn parse_year(value: &str) -> Result<u16, String> {
+    let year: u16 = parse_value("year", value)?;
+    Ok(if year < 100 { 2000 + year } else { year })
+}
+
+pub fn parse_value<T: FromStr>(name: &str, value: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("invalid {name}: '{value}'"))
+}
+
+/// Parses the timeout given in seconds, e.g. `2.5`.
+pub fn parse_timeout(value: &str) -> Result<Duration, String> {
+    value
+        .parse::<f64>()
+        .ok()
+        .and_then(|seconds| Duration::try_from_secs_f64(seconds).ok())
+        .filter(|timeout| !timeout.is_zero())
+        .ok_or_else(|| format!("invalid timeout: '{value}'"))
+}
+
+/// Parses optional `[<year> [<day>]]` arguments selecting the puzzles.
+pub fn parse_puzzles(positional: &[String]) -> Result<(Option<u16>, Option<u8>), String> {
+    match positional {
+        [] => Ok((None, None)),
+        [year] => Ok((Some(parse_year(year)?), None)),
+        [year, day] => Ok((Some(parse_year(year)?), Some(parse_value("day", day)?))),
+        _ => Err("expected [<year> [<day>]]".to_owned()),
+    }
+}
+
+pub fn read_input(path: Option<&str>) -> Result<String, String> {
+    match path {
+        None | Some("-") => {
+            let mut content = String::new();
+            std::io::stdin()
+                .read_to_string(&mut content)
+                .map_err(|e| format!("cannot read stdin: {e}"))?;
+            Ok(content)
+        }
+        Some(path) => fs::read_to_string(path).map_err(|e| format!("cannot read {path}: {e}")),
+    }
+}
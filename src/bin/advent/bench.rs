@@ -0,0 +1,306 @@
+use crate::{
+    args::{parse_puzzles, parse_value},
+    format,
+};
+use advent::answers::{self, AnswerDb};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs,
+    io::Write,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+/// Run times of a part, in nanoseconds.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct Timing {
+    year: u16,
+    day: u8,
+    part: u8,
+    min: u64,
+    median: u64,
+    max: u64,
+}
+
+impl Timing {
+    fn new(year: u16, day: u8, part: u8, mut durations: Vec<Duration>) -> Self {
+        durations.sort();
+        let nanos = |d: Duration| u64::try_from(d.as_nanos()).unwrap_or(u64::MAX);
+        let mid = durations.len() / 2;
+        let median = if durations.len().is_multiple_of(2) {
+            (nanos(durations[mid - 1]) + nanos(durations[mid])) / 2
+        } else {
+            nanos(durations[mid])
+        };
+        Self {
+            year,
+            day,
+            part,
+            min: nanos(durations[0]),
+            median,
+            max: nanos(durations[durations.len() - 1]),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct Report {
+    /// Seconds since the Unix epoch.
+    timestamp: u64,
+    runs: usize,
+    timings: Vec<Timing>,
+}
+
+impl Report {
+    /// Returns the change of the median time in percent compared to the same part in `previous`.
+    fn change(timing: &Timing, previous: &Report) -> Option<f64> {
+        let old = previous
+            .timings
+            .iter()
+            .find(|t| (t.year, t.day, t.part) == (timing.year, timing.day, timing.part))?;
+        (old.median > 0).then(|| (timing.median as f64 / old.median as f64 - 1.0) * 100.0)
+    }
+
+    /// Formats the report as a Markdown table, comparing it to the previous one if there is any.
+    fn to_markdown(&self, previous: Option<&Report>, threshold: f64) -> String {
+        let mut rows = vec![vec![
+            "Puzzle".to_owned(),
+            "Part".to_owned(),
+            "Min".to_owned(),
+            "Median".to_owned(),
+            "Max".to_owned(),
+            "Change".to_owned(),
+        ]];
+        for timing in &self.timings {
+            let change = match previous.and_then(|previous| Report::change(timing, previous)) {
+                Some(change) if change > threshold => format!("{change:+.1}% regressed"),
+                Some(change) => format!("{change:+.1}%"),
+                None => "-".to_owned(),
+            };
+            rows.push(vec![
+                format!("{} day {}", timing.year, timing.day),
+                timing.part.to_string(),
+                format::nanos(timing.min),
+                format::nanos(timing.median),
+                format::nanos(timing.max),
+                change,
+            ]);
+        }
+
+        format::table(&rows)
+    }
+
+    /// Returns the parts whose median time grew by more than `threshold` percent.
+    fn regressions<'a>(
+        &'a self,
+        previous: &'a Report,
+        threshold: f64,
+    ) -> impl Iterator<Item = &'a Timing> + 'a {
+        self.timings
+            .iter()
+            .filter(move |t| Report::change(t, previous).is_some_and(|c| c > threshold))
+    }
+}
+
+/// Reads the last report appended to the history file, which keeps one JSON report per line.
+fn read_last_report(path: &str) -> Result<Option<Report>, String> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(format!("cannot read {path}: {e}")),
+    };
+    match content.lines().rev().find(|line| !line.trim().is_empty()) {
+        Some(line) => serde_json::from_str(line)
+            .map(Some)
+            .map_err(|e| format!("{path}: {e}")),
+        None => Ok(None),
+    }
+}
+
+pub fn bench(mut args: impl Iterator<Item = String>) -> Result<bool, String> {
+    let mut positional = Vec::new();
+    let mut part: Option<u8> = None;
+    let mut runs: usize = 10;
+    let mut json_path = None;
+    let mut markdown_path = None;
+    let mut history_path = None;
+    let mut threshold = 10.0;
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| args.next().ok_or(format!("{name} requires a value"));
+        match arg.as_str() {
+            "--part" | "-p" => part = Some(parse_value("part", &value("--part")?)?),
+            "--runs" => runs = parse_value("number of runs", &value("--runs")?)?,
+            "--json" => json_path = Some(value("--json")?),
+            "--markdown" => markdown_path = Some(value("--markdown")?),
+            "--history" => history_path = Some(value("--history")?),
+            "--threshold" => threshold = parse_value("threshold", &value("--threshold")?)?,
+            _ => positional.push(arg),
+        }
+    }
+    let (year, day) = parse_puzzles(&positional)?;
+    let parts = match part {
+        Some(part @ (1 | 2)) => vec![part],
+        Some(part) => return Err(format!("there is no part {part}")),
+        None => vec![1, 2],
+    };
+    if runs == 0 {
+        return Err("invalid number of runs: '0'".to_owned());
+    }
+
+    let path = answers::DEFAULT_PATH;
+    let content = fs::read_to_string(path).map_err(|e| format!("cannot read {path}: {e}"))?;
+    let db = AnswerDb::parse(&content).map_err(|e| format!("{path}: {e}"))?;
+
+    let mut timings = Vec::new();
+    let registry = advent::registry();
+    let solutions = registry
+        .iter()
+        .filter(|s| year.is_none_or(|year| s.year() == year))
+        .filter(|s| day.is_none_or(|day| s.day() == day));
+    for solution in solutions {
+        let (year, day) = (solution.year(), solution.day());
+        let Some(entry) = db.entry(year, day, "actual") else {
+            continue;
+        };
+        let file_content = fs::read_to_string(&entry.input)
+            .map_err(|e| format!("cannot read {}: {e}", entry.input))?;
+        let mut params = solution.params();
+        for (name, value) in &entry.params {
+            params
+                .set(name, value)
+                .map_err(|e| format!("{year} day {day}: {e}"))?;
+        }
+        for &part in &parts {
+            let mut durations = Vec::with_capacity(runs);
+            for _ in 0..runs {
+                let start = Instant::now();
+                let answer = solution
+                    .solve(part, &file_content, &params)
+                    .expect("part is checked above");
+                durations.push(start.elapsed());
+                answer.map_err(|e| e.to_string())?;
+            }
+            timings.push(Timing::new(year, day, part, durations));
+        }
+    }
+
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+    let report = Report {
+        timestamp,
+        runs,
+        timings,
+    };
+    let previous = match &history_path {
+        Some(path) => read_last_report(path)?,
+        None => None,
+    };
+    let markdown = report.to_markdown(previous.as_ref(), threshold);
+    print!("{markdown}");
+
+    if let Some(path) = json_path {
+        let json = serde_json::to_string_pretty(&report).expect("report is serializable");
+        fs::write(&path, json + "\n").map_err(|e| format!("cannot write {path}: {e}"))?;
+    }
+    if let Some(path) = markdown_path {
+        fs::write(&path, &markdown).map_err(|e| format!("cannot write {path}: {e}"))?;
+    }
+    if let Some(path) = &history_path {
+        let line = serde_json::to_string(&report).expect("report is serializable");
+        fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .and_then(|mut file| writeln!(file, "{line}"))
+            .map_err(|e| format!("cannot write {path}: {e}"))?;
+    }
+
+    let regressions = previous.map_or(0, |previous| {
+        report.regressions(&previous, threshold).count()
+    });
+    if regressions > 0 {
+        println!("{regressions} parts regressed by more than {threshold}%");
+    }
+    Ok(regressions == 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn timing(day: u8, part: u8, median: u64) -> Timing {
+        Timing {
+            year: 2022,
+            day,
+            part,
+            min: median / 2,
+            median,
+            max: median * 2,
+        }
+    }
+
+    #[test]
+    fn test_timing() {
+        let millis = |ms: &[u64]| ms.iter().map(|&ms| Duration::from_millis(ms)).collect();
+        assert_eq!(
+            Timing::new(2022, 9, 1, millis(&[3, 1, 2])),
+            Timing {
+                year: 2022,
+                day: 9,
+                part: 1,
+                min: 1_000_000,
+                median: 2_000_000,
+                max: 3_000_000
+            }
+        );
+        assert_eq!(
+            Timing::new(2022, 9, 1, millis(&[4, 1, 2, 8])).median,
+            3_000_000
+        );
+    }
+
+    #[test]
+    fn test_markdown() {
+        let previous = Report {
+            timestamp: 0,
+            runs: 10,
+            timings: vec![timing(15, 1, 1000), timing(15, 2, 2000)],
+        };
+        let report = Report {
+            timestamp: 1,
+            runs: 10,
+            timings: vec![timing(15, 1, 1050), timing(15, 2, 3000), timing(16, 1, 10)],
+        };
+        assert_eq!(
+            report.to_markdown(Some(&previous), 10.0),
+            "\
+| Puzzle      | Part | Min     | Median  | Max     | Change           |
+| ----------- | ---- | ------- | ------- | ------- | ---------------- |
+| 2022 day 15 | 1    | 525 ns  | 1.05 µs | 2.10 µs | +5.0%            |
+| 2022 day 15 | 2    | 1.50 µs | 3.00 µs | 6.00 µs | +50.0% regressed |
+| 2022 day 16 | 1    | 5 ns    | 10 ns   | 20 ns   | -                |
+"
+        );
+        let regressions = report.regressions(&previous, 10.0).collect::<Vec<_>>();
+        assert_eq!(regressions, vec![&timing(15, 2, 3000)]);
+        assert_eq!(report.regressions(&previous, 60.0).count(), 0);
+    }
+
+    #[test]
+    fn test_history() {
+        let path = std::env::temp_dir().join("advent-bench-history-test.jsonl");
+        let path = path.to_str().unwrap();
+        fs::write(path, "").unwrap();
+        assert_eq!(read_last_report(path).unwrap(), None);
+        let report = |timestamp| Report {
+            timestamp,
+            runs: 1,
+            timings: vec![timing(9, 1, 100)],
+        };
+        let lines = [report(1), report(2)].map(|r| serde_json::to_string(&r).unwrap());
+        fs::write(path, lines.join("\n") + "\n").unwrap();
+        assert_eq!(read_last_report(path).unwrap(), Some(report(2)));
+        fs::remove_file(path).unwrap();
+        assert_eq!(read_last_report(path).unwrap(), None);
+    }
+}
@@ -0,0 +1,69 @@
+/// Formats the rows as a Markdown table with aligned columns, the first row is the header.
+pub fn table(rows: &[Vec<String>]) -> String {
+    let columns = rows.iter().map(Vec::len).max().unwrap_or(0);
+    let widths = (0..columns)
+        .map(|i| {
+            rows.iter()
+                .filter_map(|row| row.get(i))
+                .map(|cell| cell.chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect::<Vec<_>>();
+    let line = |cells: Vec<String>| format!("| {} |\n", cells.join(" | "));
+    let mut res = String::new();
+    for (ind, row) in rows.iter().enumerate() {
+        res.push_str(&line(
+            widths
+                .iter()
+                .enumerate()
+                .map(|(i, &width)| format!("{:<width$}", row.get(i).map_or("", String::as_str)))
+                .collect(),
+        ));
+        if ind == 0 {
+            res.push_str(&line(widths.iter().map(|&w| "-".repeat(w)).collect()));
+        }
+    }
+    res
+}
+
+pub fn nanos(nanos: u64) -> String {
+    let nanos = nanos as f64;
+    if nanos < 1e3 {
+        format!("{nanos} ns")
+    } else if nanos < 1e6 {
+        format!("{:.2} µs", nanos / 1e3)
+    } else if nanos < 1e9 {
+        format!("{:.2} ms", nanos / 1e6)
+    } else {
+        format!("{:.2} s", nanos / 1e9)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_table() {
+        let rows = [vec!["Puzzle", "Part"], vec!["2022 day 15", "1"], vec!["x"]]
+            .map(|row| row.into_iter().map(str::to_owned).collect());
+        assert_eq!(
+            table(&rows),
+            "\
+| Puzzle      | Part |
+| ----------- | ---- |
+| 2022 day 15 | 1    |
+| x           |      |
+"
+        );
+    }
+
+    #[test]
+    fn test_nanos() {
+        assert_eq!(nanos(999), "999 ns");
+        assert_eq!(nanos(12_345), "12.35 µs");
+        assert_eq!(nanos(2_500_000), "2.50 ms");
+        assert_eq!(nanos(61_000_000_000), "61.00 s");
+    }
+}
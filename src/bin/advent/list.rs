@@ -0,0 +1,15 @@
+pub fn list() -> Result<(), String> {
+    for solution in advent::registry().iter() {
+        print!(
+            "{} day {:>2}: {}",
+            solution.year(),
+            solution.day(),
+            solution.title()
+        );
+        for parameter in solution.parameters() {
+            print!(" [{}={}]", parameter.name, parameter.default);
+        }
+        println!();
+    }
+    Ok(())
+}
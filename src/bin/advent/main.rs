@@ -0,0 +1,83 @@
+mod animate;
+mod args;
+mod bench;
+mod format;
+mod list;
+mod new;
+mod render;
+mod run;
+mod trace;
+mod verify;
+
+use std::process::ExitCode;
+
+const USAGE: &str = "Usage:
+    advent list
+    advent run <year> <day> [--part 1|2] [--param name=value]... [--timeout <seconds>]
+               [--trace | --trace-json <file>] [input]
+    advent run --all [--year <year>] [--part 1|2] [--timeout <seconds>]
+                     [--trace | --trace-json <file>]
+    advent verify [<year> [<day>]] [--record] [--answers <file>]
+    advent bench [<year> [<day>]] [--part 1|2] [--runs N] [--json <file>] [--markdown <file>]
+                 [--history <file>] [--threshold <percent>]
+    advent new <year> <day> [--parts 1|2] [--templates <dir>]
+    advent render <year> <day> [--param name=value]... [--scale N] [--palette <colors>]
+                  [--output <file>] [input]
+    advent animate <year> <day> [--param name=value]... [--fps N] [--every N] [--frames N]
+                   [--scale N] [--palette <colors>] [--output <dir>] [input]
+
+If input is omitted or equals to '-', it is read from stdin.
+Parameters of the puzzles and their defaults are shown by 'advent list'.
+run --all solves the actual inputs of the puzzles in parallel and checks them against answers.toml.
+Parts running longer than --timeout are stopped and reported as TIMEOUT.
+--trace prints the events emitted by the solutions to stderr, --trace-json writes them to the file.
+verify checks the answers stored in answers.toml, --record stores the missing ones.
+bench times the solutions on their actual inputs, --history compares the run with the last one
+stored in the file and appends it there, parts slower by more than --threshold (10) percent fail.
+new creates the module and the inputs of a puzzle, skipping what already exists;
+the templates are taken from day.rs.tpl and tests.rs.tpl in <dir> (templates).
+render saves the picture of a puzzle as a .pbm or .ppm image (y22d10.ppm for 2022 day 10),
+--palette replaces its colors in order, e.g. '#000000,ffffff', --scale enlarges the pixels.
+animate plays the simulation of a puzzle in the terminal at --fps (30) frames per second,
+showing every --every (1) steps and at most --frames frames; with --output it saves the frames
+as numbered .ppm images in the directory instead, at most 1000 of them unless --frames is given.";
+
+fn main() -> ExitCode {
+    let mut args = std::env::args().skip(1);
+    let result = match args.next().as_deref() {
+        Some("list") => list::list(),
+        Some("run") => match run::run(args) {
+            Ok(true) => Ok(()),
+            Ok(false) => return ExitCode::FAILURE,
+            Err(message) => Err(message),
+        },
+        Some("new") => new::new(args),
+        Some("render") => render::render(args),
+        Some("animate") => animate::animate(args),
+        Some("verify") => match verify::verify(args) {
+            Ok(true) => Ok(()),
+            Ok(false) => return ExitCode::FAILURE,
+            Err(message) => Err(message),
+        },
+        Some("bench") => match bench::bench(args) {
+            Ok(true) => Ok(()),
+            Ok(false) => return ExitCode::FAILURE,
+            Err(message) => Err(message),
+        },
+        Some("help" | "--help" | "-h") => {
+            println!("{USAGE}");
+            Ok(())
+        }
+        Some(command) => Err(format!("unknown command '{command}'")),
+        None => Err("command is not specified".to_owned()),
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(message) => {
+            eprintln!("error: {message}");
+            eprintln!("run 'advent help' to see the usage");
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,222 @@
+use crate::args::{parse_value, parse_year};
+use advent::answers::{self, AnswerDb};
+use std::{fs, path::Path};
+
+const TEMPLATES: [(&str, &str); 2] = [
+    ("day.rs.tpl", include_str!("templates/day.rs.tpl")),
+    ("tests.rs.tpl", include_str!("templates/tests.rs.tpl")),
+];
+
+pub fn new(mut args: impl Iterator<Item = String>) -> Result<(), String> {
+    let mut positional = Vec::new();
+    let mut parts: u8 = 2;
+    let mut templates_dir = "templates".to_owned();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--parts" => {
+                let value = args.next().ok_or("--parts requires a value")?;
+                parts = parse_value("number of parts", &value)?;
+            }
+            "--templates" => {
+                templates_dir = args.next().ok_or("--templates requires a value")?;
+            }
+            _ => positional.push(arg),
+        }
+    }
+
+    let (year, day) = match positional.as_slice() {
+        [year, day] => (parse_year(year)?, parse_value::<u8>("day", day)?),
+        _ => return Err("expected <year> <day>".to_owned()),
+    };
+    if !(1..=25).contains(&day) {
+        return Err(format!("invalid day: '{day}'"));
+    }
+    if !(1..=2).contains(&parts) {
+        return Err(format!("invalid number of parts: '{parts}'"));
+    }
+
+    let module = format!("y{:02}d{:02}", year % 100, day);
+    let (year_text, day_text) = (year.to_string(), day.to_string());
+    let vars = [
+        ("year", year_text.as_str()),
+        ("day", day_text.as_str()),
+        ("module", module.as_str()),
+    ];
+    let [day_template, tests_template] = load_templates(&templates_dir)?;
+    let render = |template: &str| render(template, &vars, parts);
+
+    create_file(
+        &format!("src/{module}.rs"),
+        &(render(&day_template) + &render(&tests_template)),
+    )?;
+    create_file(&format!("src/{module}/example.txt"), "")?;
+    create_file(&format!("benches/{module}.txt"), "")?;
+    update_file("src/lib.rs", |content| Ok(add_module(content, &module)))?;
+    update_file(answers::DEFAULT_PATH, |content| {
+        let mut db = AnswerDb::parse(content)?;
+        let example = db.add_input(year, day, "example", &format!("src/{module}/example.txt"));
+        let actual = db.add_input(year, day, "actual", &format!("benches/{module}.txt"));
+        Ok((example || actual).then(|| db.to_string()))
+    })?;
+
+    Ok(())
+}
+
+/// Reads the templates from the directory, the built-in ones are used for missing files.
+fn load_templates(dir: &str) -> Result<[String; 2], String> {
+    let mut templates = TEMPLATES.map(|(_, template)| template.to_owned());
+    for ((name, _), template) in TEMPLATES.iter().zip(&mut templates) {
+        let path = Path::new(dir).join(name);
+        if path.exists() {
+            *template = fs::read_to_string(&path)
+                .map_err(|e| format!("cannot read {}: {e}", path.display()))?;
+        }
+    }
+    Ok(templates)
+}
+
+/// Replaces `{{name}}` with the values of the variables. Lines between `{{#each part}}`
+/// and `{{/each}}` are repeated for every part, `{{#each missing_part}}` is used for the
+/// parts that are not solved, `{{part}}` is the number of the part inside of them.
+fn render(template: &str, vars: &[(&str, &str)], parts: u8) -> String {
+    let substitute = |line: &str, part: Option<u8>| {
+        let mut line = line.to_owned();
+        for (name, value) in vars {
+            line = line.replace(&format!("{{{{{name}}}}}"), value);
+        }
+        if let Some(part) = part {
+            line = line.replace("{{part}}", &part.to_string());
+        }
+        line
+    };
+
+    let mut res = String::new();
+    let mut lines = template.lines();
+    while let Some(line) = lines.next() {
+        let block_parts = match line.trim() {
+            "{{#each part}}" => 1..=parts,
+            "{{#each missing_part}}" => (parts + 1)..=2,
+            _ => {
+                res.push_str(&substitute(line, None));
+                res.push('\n');
+                continue;
+            }
+        };
+        let block = lines
+            .by_ref()
+            .take_while(|line| line.trim() != "{{/each}}")
+            .collect::<Vec<_>>();
+        for part in block_parts {
+            for line in &block {
+                res.push_str(&substitute(line, Some(part)));
+                res.push('\n');
+            }
+        }
+    }
+    res
+}
+
+fn create_file(path: &str, content: &str) -> Result<(), String> {
+    let path = Path::new(path);
+    if path.exists() {
+        println!("{} already exists, skipped", path.display());
+        return Ok(());
+    }
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| format!("cannot create {}: {e}", dir.display()))?;
+    }
+    fs::write(path, content).map_err(|e| format!("cannot write {}: {e}", path.display()))?;
+    println!("created {}", path.display());
+    Ok(())
+}
+
+/// Rewrites the file with the content returned by `update`, `None` means there is nothing to change.
+fn update_file(
+    path: &str,
+    update: impl FnOnce(&str) -> Result<Option<String>, String>,
+) -> Result<(), String> {
+    let content = fs::read_to_string(path).map_err(|e| format!("cannot read {path}: {e}"))?;
+    match update(&content).map_err(|e| format!("{path}: {e}"))? {
+        Some(content) => {
+            fs::write(path, content).map_err(|e| format!("cannot write {path}: {e}"))?;
+            println!("updated {path}");
+        }
+        None => println!("{path} is up to date"),
+    }
+    Ok(())
+}
+
+/// Declares the module and registers its solution in lib.rs unless it is already done.
+fn add_module(content: &str, module_name: &str) -> Option<String> {
+    let declaration = format!("pub mod {module_name};");
+    let registration = format!("    {module_name}::register(&mut registry);");
+    let mut module_declared = content.lines().any(|line| line == declaration);
+    let mut module_registered = content.lines().any(|line| line == registration);
+    if module_declared && module_registered {
+        return None;
+    }
+
+    let mut res = String::new();
+    for line in content.lines() {
+        if !module_declared {
+            // modules are declared in alphabetical order at the top of lib.rs
+            let is_after = match line.strip_prefix("pub mod ") {
+                Some(m) => m.trim_end_matches(';') > module_name,
+                None => !res.is_empty(),
+            };
+            if is_after {
+                res.push_str(&declaration);
+                res.push('\n');
+                module_declared = true;
+            }
+        }
+        if !module_registered && line == "    registry" {
+            res.push_str(&registration);
+            res.push('\n');
+            module_registered = true;
+        }
+        res.push_str(line);
+        res.push('\n');
+    }
+    Some(res)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let template = "mod {{module}};\n{{#each part}}\nfn part{{part}}() {}\n{{/each}}\n{{#each missing_part}}\nfn stub{{part}}() {}\n{{/each}}\n// {{unknown}}\n";
+        let vars = [("module", "y22d20")];
+        assert_eq!(
+            render(template, &vars, 2),
+            "mod y22d20;\nfn part1() {}\nfn part2() {}\n// {{unknown}}\n"
+        );
+        assert_eq!(
+            render(template, &vars, 1),
+            "mod y22d20;\nfn part1() {}\nfn stub2() {}\n// {{unknown}}\n"
+        );
+    }
+
+    #[test]
+    fn test_builtin_templates() {
+        let vars = [("year", "2022"), ("day", "20"), ("module", "y22d20")];
+        let [day, tests] = TEMPLATES.map(|(_, template)| render(template, &vars, 1));
+        assert!(day.contains("pub fn solve_task1(_file_content: &str) -> usize {"));
+        assert!(day.contains("        Ok(solve_task1(file_content).into())"));
+        assert!(day.contains("    fn part2(&self, _file_content: &str, _params: &Params)"));
+        assert!(!day.contains("{{"));
+        assert!(tests.contains("include_str!(\"./y22d20/example.txt\")"));
+        assert!(!tests.contains("test_task2"));
+    }
+
+    #[test]
+    fn test_add_module() {
+        let content = "pub mod day1;\npub mod y22d19;\n\npub fn registry() -> Registry {\n    let mut registry = Registry::default();\n    day1::register(&mut registry);\n    y22d19::register(&mut registry);\n    registry\n}\n";
+        let updated = add_module(content, "y22d20").unwrap();
+        assert_eq!(updated, "pub mod day1;\npub mod y22d19;\npub mod y22d20;\n\npub fn registry() -> Registry {\n    let mut registry = Registry::default();\n    day1::register(&mut registry);\n    y22d19::register(&mut registry);\n    y22d20::register(&mut registry);\n    registry\n}\n");
+        assert_eq!(add_module(&updated, "y22d20"), None);
+        assert_eq!(add_module(content, "y15d01").unwrap(), "pub mod day1;\npub mod y15d01;\npub mod y22d19;\n\npub fn registry() -> Registry {\n    let mut registry = Registry::default();\n    day1::register(&mut registry);\n    y22d19::register(&mut registry);\n    y15d01::register(&mut registry);\n    registry\n}\n");
+    }
+}
@@ -0,0 +1,121 @@
+use crate::args::{parse_value, parse_year, read_input};
+use advent::render::Rgb;
+use std::{fs::File, io::BufWriter, io::Write};
+
+pub fn render(mut args: impl Iterator<Item = String>) -> Result<(), String> {
+    let mut positional = Vec::new();
+    let mut assignments = Vec::new();
+    let mut scale = 1;
+    let mut palette = None;
+    let mut output = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--param" | "-P" => {
+                assignments.push(args.next().ok_or("--param requires name=value")?);
+            }
+            "--scale" => {
+                let value = args.next().ok_or("--scale requires a value")?;
+                scale = parse_value("scale", &value)?;
+                if scale == 0 {
+                    return Err("invalid scale: '0'".to_owned());
+                }
+            }
+            "--palette" => {
+                let value = args.next().ok_or("--palette requires colors")?;
+                palette = Some(parse_palette(&value)?);
+            }
+            "--output" | "-o" => output = Some(args.next().ok_or("--output requires a file")?),
+            _ => positional.push(arg),
+        }
+    }
+
+    let (year, day, input) = match positional.as_slice() {
+        [year, day] => (year, day, None),
+        [year, day, input] => (year, day, Some(input.as_str())),
+        _ => return Err("expected <year> <day> [input]".to_owned()),
+    };
+    let year = parse_year(year)?;
+    let day: u8 = parse_value("day", day)?;
+    let solution = advent::registry()
+        .get(year, day)
+        .ok_or_else(|| format!("{year} day {day} is not solved yet"))?;
+
+    let mut params = solution.params();
+    for assignment in &assignments {
+        params
+            .assign(assignment)
+            .map_err(|e| format!("{year} day {day}: {e}"))?;
+    }
+
+    let output = output.unwrap_or_else(|| format!("y{}d{day:02}.ppm", year % 100));
+    let is_pbm = match output.rsplit_once('.').map(|(_, extension)| extension) {
+        Some("pbm") => true,
+        Some("ppm") => false,
+        _ => return Err(format!("{output}: expected .pbm or .ppm file")),
+    };
+
+    let file_content = read_input(input)?;
+    let image = solution
+        .render(&file_content, &params)
+        .ok_or_else(|| format!("{year} day {day} has no picture"))?
+        .map_err(|e| e.to_string())?;
+    let image = match palette {
+        Some(palette) => image.with_palette(palette),
+        None => image,
+    };
+
+    let file = File::create(&output).map_err(|e| format!("cannot create {output}: {e}"))?;
+    let mut out = BufWriter::new(file);
+    let written = if is_pbm {
+        image.write_pbm(&mut out, scale)
+    } else {
+        image.write_ppm(&mut out, scale)
+    };
+    written
+        .and_then(|()| out.flush())
+        .map_err(|e| format!("cannot write {output}: {e}"))?;
+    println!(
+        "{year} day {day}: {output}, {}x{} pixels",
+        image.width() * scale,
+        image.height() * scale
+    );
+    Ok(())
+}
+
+/// Parses comma separated colors like `#1e1e28,8c8c8c`.
+pub fn parse_palette(value: &str) -> Result<Vec<Rgb>, String> {
+    value
+        .split(',')
+        .map(|color| {
+            let hex = color.trim().trim_start_matches('#');
+            let invalid = || format!("invalid color: '{color}'");
+            if hex.len() != 6 || !hex.is_ascii() {
+                return Err(invalid());
+            }
+            let mut rgb = [0; 3];
+            for (ind, channel) in rgb.iter_mut().enumerate() {
+                *channel =
+                    u8::from_str_radix(&hex[ind * 2..ind * 2 + 2], 16).map_err(|_| invalid())?;
+            }
+            Ok(rgb)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_palette() {
+        assert_eq!(
+            parse_palette("#000000, ff8000").unwrap(),
+            vec![[0, 0, 0], [0xff, 0x80, 0]]
+        );
+        assert_eq!(parse_palette("#fff").unwrap_err(), "invalid color: '#fff'");
+        assert_eq!(
+            parse_palette("gg0000").unwrap_err(),
+            "invalid color: 'gg0000'"
+        );
+    }
+}
@@ -0,0 +1,435 @@
+use crate::{
+    args::{parse_timeout, parse_value, parse_year, read_input},
+    format,
+    trace::Target,
+};
+use advent::{
+    answers::{self, AnswerDb},
+    parsing::ParseError,
+    solution::{
+        cancel::{self, Cancelled},
+        Answer, Params, Solution,
+    },
+};
+use rayon::prelude::*;
+use std::{
+    any::Any,
+    fs,
+    panic::{self, AssertUnwindSafe},
+    time::{Duration, Instant},
+};
+
+pub fn run(mut args: impl Iterator<Item = String>) -> Result<bool, String> {
+    let mut positional = Vec::new();
+    let mut part: Option<u8> = None;
+    let mut assignments = Vec::new();
+    let mut all = false;
+    let mut all_year = None;
+    let mut timeout = None;
+    let mut trace = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--part" | "-p" => {
+                let value = args.next().ok_or("--part requires a value")?;
+                part = Some(parse_value("part", &value)?);
+            }
+            "--param" | "-P" => {
+                assignments.push(args.next().ok_or("--param requires name=value")?);
+            }
+            "--timeout" => {
+                let value = args.next().ok_or("--timeout requires a value")?;
+                timeout = Some(parse_timeout(&value)?);
+            }
+            "--trace" => trace = Some(Target::Stderr),
+            "--trace-json" => {
+                let path = args.next().ok_or("--trace-json requires a file")?;
+                trace = Some(Target::json(&path)?);
+            }
+            "--all" => all = true,
+            "--year" => {
+                let value = args.next().ok_or("--year requires a value")?;
+                all_year = Some(parse_year(&value)?);
+            }
+            _ => positional.push(arg),
+        }
+    }
+
+    if all {
+        if !positional.is_empty() || !assignments.is_empty() {
+            return Err("--all takes neither puzzles, inputs nor parameters".to_owned());
+        }
+        return run_all(all_year, part, timeout, trace);
+    }
+    if all_year.is_some() {
+        return Err("--year is used only with --all".to_owned());
+    }
+
+    let (year, day, input) = match positional.as_slice() {
+        [year, day] => (year, day, None),
+        [year, day, input] => (year, day, Some(input.as_str())),
+        _ => return Err("expected <year> <day> [input]".to_owned()),
+    };
+    let year = parse_year(year)?;
+    let day: u8 = parse_value("day", day)?;
+
+    let solution = advent::registry()
+        .get(year, day)
+        .ok_or_else(|| format!("{year} day {day} is not solved yet"))?;
+
+    let mut params = solution.params();
+    for assignment in &assignments {
+        params
+            .assign(assignment)
+            .map_err(|e| format!("{year} day {day}: {e}"))?;
+    }
+
+    let parts = parse_parts(part).map_err(|e| format!("{year} day {day}: {e}"))?;
+
+    let file_content = read_input(input)?;
+    println!("{year} day {day}: {}", solution.title());
+    let mut timed_out = false;
+    for part in parts {
+        let params = match &trace {
+            Some(target) => params.clone().with_trace(target.trace(year, day, part)),
+            None => params.clone(),
+        };
+        let Some(answer) = solve_with_timeout(solution, part, &file_content, &params, timeout)
+        else {
+            println!("Part {part}: TIMEOUT");
+            timed_out = true;
+            continue;
+        };
+        let answer = answer.map_err(|e| e.to_string())?;
+        if answer.is_multiline() {
+            println!("Part {part}:\n{}", answer.to_string().trim_end());
+        } else {
+            println!("Part {part}: {answer}");
+        }
+    }
+
+    Ok(!timed_out)
+}
+
+/// Solves the part, `None` means that the solution was cancelled after the timeout.
+fn solve_with_timeout(
+    solution: &dyn Solution,
+    part: u8,
+    file_content: &str,
+    params: &Params,
+    timeout: Option<Duration>,
+) -> Option<Result<Answer, ParseError>> {
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        cancel::with_timeout(timeout, |cancel| {
+            let params = params.clone().with_cancel(cancel.clone());
+            solution
+                .solve(part, file_content, &params)
+                .expect("part is checked above")
+        })
+    }));
+    match result {
+        Ok(answer) => Some(answer),
+        Err(payload) if payload.is::<Cancelled>() => None,
+        Err(payload) => panic::resume_unwind(payload),
+    }
+}
+
+/// Returns the message the solution panicked with.
+pub fn panic_message(payload: &(dyn Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|message| message.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic".to_owned())
+}
+
+fn parse_parts(part: Option<u8>) -> Result<Vec<u8>, String> {
+    match part {
+        Some(part @ (1 | 2)) => Ok(vec![part]),
+        Some(part) => Err(format!("there is no part {part}")),
+        None => Ok(vec![1, 2]),
+    }
+}
+
+/// What happened to a part when all of the puzzles were run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Status {
+    Pass,
+    Fail(String),
+    Missing,
+    NoInput,
+    Error(String),
+    Panic(String),
+    Timeout,
+}
+
+impl Status {
+    fn is_failure(&self) -> bool {
+        matches!(
+            self,
+            Status::Fail(_) | Status::Error(_) | Status::Panic(_) | Status::Timeout
+        )
+    }
+}
+
+impl std::fmt::Display for Status {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Status::Pass => write!(f, "pass"),
+            Status::Fail(expected) => write!(f, "FAIL, expected {expected}"),
+            Status::Missing => write!(f, "missing"),
+            Status::NoInput => write!(f, "no input"),
+            Status::Error(e) => write!(f, "ERROR, {e}"),
+            Status::Panic(message) => write!(f, "PANIC, {message}"),
+            Status::Timeout => write!(f, "TIMEOUT"),
+        }
+    }
+}
+
+struct Outcome {
+    year: u16,
+    day: u8,
+    part: Option<u8>,
+    answer: Option<Answer>,
+    elapsed: Option<Duration>,
+    status: Status,
+}
+
+/// A puzzle with its actual input, ready to be solved.
+struct Job {
+    solution: &'static dyn Solution,
+    params: Params,
+    file_content: String,
+    answers: [Option<String>; 2],
+    timeout: Option<Duration>,
+    trace: Option<Target>,
+}
+
+/// Solves the `actual` inputs of all of the puzzles from the answer database in parallel.
+fn run_all(
+    year: Option<u16>,
+    part: Option<u8>,
+    timeout: Option<Duration>,
+    trace: Option<Target>,
+) -> Result<bool, String> {
+    let parts = parse_parts(part)?;
+    let path = answers::DEFAULT_PATH;
+    let content = fs::read_to_string(path).map_err(|e| format!("cannot read {path}: {e}"))?;
+    let db = AnswerDb::parse(&content).map_err(|e| format!("{path}: {e}"))?;
+
+    let mut jobs = Vec::new();
+    let mut outcomes = Vec::new();
+    let registry = advent::registry();
+    for solution in registry
+        .iter()
+        .filter(|s| year.is_none_or(|year| s.year() == year))
+    {
+        let (year, day) = (solution.year(), solution.day());
+        let Some(entry) = db.entry(year, day, "actual") else {
+            outcomes.push(Outcome {
+                year,
+                day,
+                part: None,
+                answer: None,
+                elapsed: None,
+                status: Status::NoInput,
+            });
+            continue;
+        };
+        let file_content = fs::read_to_string(&entry.input)
+            .map_err(|e| format!("cannot read {}: {e}", entry.input))?;
+        let mut params = solution.params();
+        for (name, value) in &entry.params {
+            params
+                .set(name, value)
+                .map_err(|e| format!("{year} day {day}: {e}"))?;
+        }
+        jobs.push(Job {
+            solution,
+            params,
+            file_content,
+            answers: entry.answers.clone(),
+            timeout,
+            trace: trace.clone(),
+        });
+    }
+
+    // the panics are reported in the summary instead of being printed by the default hook
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    let start = Instant::now();
+    outcomes.par_extend(
+        jobs.par_iter()
+            .flat_map_iter(|job| parts.iter().map(move |&part| solve(job, part))),
+    );
+    let elapsed = start.elapsed();
+    panic::set_hook(hook);
+    outcomes.sort_by_key(|o| (o.year, o.day, o.part));
+
+    let mut rows = vec![["Puzzle", "Part", "Answer", "Time", "Status"]
+        .map(str::to_owned)
+        .to_vec()];
+    for outcome in &outcomes {
+        rows.push(vec![
+            format!("{} day {}", outcome.year, outcome.day),
+            outcome.part.map_or("-".to_owned(), |part| part.to_string()),
+            outcome.answer.as_ref().map_or("-".to_owned(), show),
+            outcome.elapsed.map_or("-".to_owned(), |elapsed| {
+                format::nanos(u64::try_from(elapsed.as_nanos()).unwrap_or(u64::MAX))
+            }),
+            outcome.status.to_string(),
+        ]);
+    }
+    print!("{}", format::table(&rows));
+
+    let count = |f: fn(&Status) -> bool| outcomes.iter().filter(|o| f(&o.status)).count();
+    let failed = count(Status::is_failure);
+    println!(
+        "{} passed, {} failed, {} missing in {}",
+        count(|s| *s == Status::Pass),
+        failed,
+        count(|s| *s == Status::Missing),
+        format::nanos(u64::try_from(elapsed.as_nanos()).unwrap_or(u64::MAX)),
+    );
+    Ok(failed == 0)
+}
+
+fn solve(job: &Job, part: u8) -> Outcome {
+    let (year, day) = (job.solution.year(), job.solution.day());
+    let start = Instant::now();
+    let params = match &job.trace {
+        Some(target) => job.params.clone().with_trace(target.trace(year, day, part)),
+        None => job.params.clone(),
+    };
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        solve_with_timeout(job.solution, part, &job.file_content, &params, job.timeout)
+    }));
+    let elapsed = start.elapsed();
+    let (answer, status) = match result {
+        Ok(None) => (None, Status::Timeout),
+        Ok(Some(Ok(answer))) => {
+            let status = match &job.answers[usize::from(part) - 1] {
+                Some(expected) if answers::is_same(expected, &answer) => Status::Pass,
+                Some(expected) => Status::Fail(show(&Answer::from(expected.as_str()))),
+                None => Status::Missing,
+            };
+            (Some(answer), status)
+        }
+        Ok(Some(Err(e))) => (None, Status::Error(e.to_string())),
+        Err(payload) => (None, Status::Panic(panic_message(payload.as_ref()))),
+    };
+    Outcome {
+        year,
+        day,
+        part: Some(part),
+        answer,
+        elapsed: Some(elapsed),
+        status,
+    }
+}
+
+/// Formats the answer to fit in a table cell, art is shown only by its size.
+fn show(answer: &Answer) -> String {
+    if answer.is_multiline() {
+        let art = answer.to_string();
+        let width = art
+            .lines()
+            .map(|line| line.chars().count())
+            .max()
+            .unwrap_or(0);
+        format!("art {width}x{}", art.trim_end().lines().count())
+    } else {
+        answer.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use advent::parsing::ParseError;
+
+    struct Puzzle;
+
+    impl Solution for Puzzle {
+        fn year(&self) -> u16 {
+            2022
+        }
+        fn day(&self) -> u8 {
+            1
+        }
+        fn title(&self) -> &'static str {
+            "Test"
+        }
+        fn part1(&self, file_content: &str, _params: &Params) -> Result<Answer, ParseError> {
+            Ok(file_content.len().into())
+        }
+        fn part2(&self, _file_content: &str, _params: &Params) -> Result<Answer, ParseError> {
+            panic!("not solved");
+        }
+    }
+
+    #[test]
+    fn test_solve() {
+        let job = Job {
+            solution: &Puzzle,
+            params: Puzzle.params(),
+            file_content: "abc".to_owned(),
+            answers: [Some("4".to_owned()), None],
+            timeout: None,
+            trace: None,
+        };
+        let outcome = solve(&job, 1);
+        assert_eq!(outcome.answer, Some(Answer::from(3)));
+        assert_eq!(outcome.status, Status::Fail("4".to_owned()));
+        assert!(outcome.status.is_failure());
+
+        let hook = panic::take_hook();
+        panic::set_hook(Box::new(|_| {}));
+        let outcome = solve(&job, 2);
+        panic::set_hook(hook);
+        assert_eq!(outcome.answer, None);
+        assert_eq!(outcome.status, Status::Panic("not solved".to_owned()));
+    }
+
+    struct Stuck;
+
+    impl Solution for Stuck {
+        fn year(&self) -> u16 {
+            2022
+        }
+        fn day(&self) -> u8 {
+            2
+        }
+        fn title(&self) -> &'static str {
+            "Stuck"
+        }
+        fn part1(&self, _file_content: &str, params: &Params) -> Result<Answer, ParseError> {
+            loop {
+                params.cancel().check();
+                std::thread::yield_now();
+            }
+        }
+        fn part2(&self, _file_content: &str, _params: &Params) -> Result<Answer, ParseError> {
+            Ok(0.into())
+        }
+    }
+
+    #[test]
+    fn test_timeout() {
+        let job = Job {
+            solution: &Stuck,
+            params: Stuck.params(),
+            file_content: String::new(),
+            answers: [None, Some("0".to_owned())],
+            timeout: Some(Duration::from_millis(10)),
+            trace: None,
+        };
+        assert_eq!(solve(&job, 1).status, Status::Timeout);
+        assert_eq!(solve(&job, 2).status, Status::Pass);
+    }
+
+    #[test]
+    fn test_show() {
+        assert_eq!(show(&Answer::from(42)), "42");
+        assert_eq!(show(&Answer::Art("#.\n.#\n".to_owned())), "art 2x2");
+    }
+}
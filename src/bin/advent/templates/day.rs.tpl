@@ -0,0 +1,39 @@
+use crate::{
+    parsing::ParseError,
+    registry::Registry,
+    solution::{Answer, Params, Solution},
+};
+
+{{#each part}}
+pub fn solve_task{{part}}(_file_content: &str) -> usize {
+    0
+}
+
+{{/each}}
+pub struct Day;
+
+impl Solution for Day {
+    fn year(&self) -> u16 {
+        {{year}}
+    }
+    fn day(&self) -> u8 {
+        {{day}}
+    }
+    fn title(&self) -> &'static str {
+        "Day {{day}}"
+    }
+{{#each part}}
+    fn part{{part}}(&self, file_content: &str, _params: &Params) -> Result<Answer, ParseError> {
+        Ok(solve_task{{part}}(file_content).into())
+    }
+{{/each}}
+{{#each missing_part}}
+    fn part{{part}}(&self, _file_content: &str, _params: &Params) -> Result<Answer, ParseError> {
+        Ok("".into())
+    }
+{{/each}}
+}
+
+pub fn register(registry: &mut Registry) {
+    registry.register(&Day);
+}
@@ -0,0 +1,14 @@
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    const INPUT: &str = include_str!("./{{module}}/example.txt");
+{{#each part}}
+
+    #[test]
+    #[ignore]
+    fn test_task{{part}}() {
+        assert_eq!(format!("{}", solve_task{{part}}(INPUT)), "0");
+    }
+{{/each}}
+}
@@ -0,0 +1,72 @@
+use advent::trace::{Event, Trace};
+use std::{
+    fs::File,
+    io::Write,
+    sync::{Arc, Mutex},
+};
+
+/// Where the events emitted by the solutions are rendered.
+#[derive(Debug, Clone)]
+pub enum Target {
+    Stderr,
+    /// One JSON object per line.
+    Json(Arc<Mutex<File>>),
+}
+
+impl Target {
+    pub fn json(path: &str) -> Result<Self, String> {
+        let file = File::create(path).map_err(|e| format!("cannot create {path}: {e}"))?;
+        Ok(Target::Json(Arc::new(Mutex::new(file))))
+    }
+
+    /// Returns the trace for the part of the puzzle, the events are labeled with it.
+    pub fn trace(&self, year: u16, day: u8, part: u8) -> Trace {
+        match self {
+            Target::Stderr => {
+                Trace::new(move |event: &Event| eprintln!("{year} day {day} part {part}: {event}"))
+            }
+            Target::Json(file) => {
+                let file = file.clone();
+                Trace::new(move |event: &Event| {
+                    let line = to_json(year, day, part, event);
+                    let mut file = file.lock().unwrap_or_else(|e| e.into_inner());
+                    // a trace that cannot be written must not stop the solution
+                    let _ = writeln!(file, "{line}");
+                })
+            }
+        }
+    }
+}
+
+fn to_json(year: u16, day: u8, part: u8, event: &Event) -> String {
+    let fields = event
+        .fields
+        .iter()
+        .map(|(name, value)| (name.to_string(), value.as_str().into()))
+        .collect::<serde_json::Map<_, _>>();
+    serde_json::json!({
+        "year": year,
+        "day": day,
+        "part": part,
+        "event": event.name,
+        "fields": fields,
+    })
+    .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_json() {
+        let event = Event {
+            name: "cycles",
+            fields: vec![("rem", "5".to_owned()), ("q", "1600".to_owned())],
+        };
+        assert_eq!(
+            to_json(2022, 17, 2, &event),
+            r#"{"day":17,"event":"cycles","fields":{"q":"1600","rem":"5"},"part":2,"year":2022}"#
+        );
+    }
+}
@@ -0,0 +1,141 @@
+use crate::{args::parse_puzzles, run::panic_message};
+use advent::{
+    answers::{self, AnswerDb},
+    solution::Answer,
+};
+use std::{
+    fs,
+    panic::{self, AssertUnwindSafe},
+};
+
+#[derive(Default)]
+struct Summary {
+    passed: usize,
+    failed: usize,
+    missing: usize,
+    recorded: usize,
+}
+
+pub fn verify(mut args: impl Iterator<Item = String>) -> Result<bool, String> {
+    let mut positional = Vec::new();
+    let mut record = false;
+    let mut path = answers::DEFAULT_PATH.to_owned();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--record" => record = true,
+            "--answers" => path = args.next().ok_or("--answers requires a value")?,
+            _ => positional.push(arg),
+        }
+    }
+
+    let (year, day) = parse_puzzles(&positional)?;
+
+    let content = fs::read_to_string(&path).map_err(|e| format!("cannot read {path}: {e}"))?;
+    let mut db = AnswerDb::parse(&content).map_err(|e| format!("{path}: {e}"))?;
+    let mut summary = Summary::default();
+
+    let registry = advent::registry();
+    let solutions = registry
+        .iter()
+        .filter(|s| year.is_none_or(|year| s.year() == year))
+        .filter(|s| day.is_none_or(|day| s.day() == day));
+    // the panics are reported as failures instead of being printed by the default hook
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    for solution in solutions {
+        let (year, day) = (solution.year(), solution.day());
+        let entries = db.puzzle_entries(year, day).cloned().collect::<Vec<_>>();
+        if entries.is_empty() {
+            println!("{year} day {day:>2}: no inputs");
+            summary.missing += 2;
+            continue;
+        }
+        for entry in entries {
+            let label = format!("{year} day {day:>2} {:<8}", entry.name);
+            let mut params = solution.params();
+            if let Err(e) = entry
+                .params
+                .iter()
+                .try_for_each(|(name, value)| params.set(name, value))
+            {
+                println!("{label}: FAIL, {e}");
+                summary.failed += 2;
+                continue;
+            }
+            let file_content = match fs::read_to_string(&entry.input) {
+                Ok(file_content) => file_content,
+                Err(e) => {
+                    println!("{label}: FAIL, cannot read {}: {e}", entry.input);
+                    summary.failed += 2;
+                    continue;
+                }
+            };
+            for part in [1, 2] {
+                let label = format!("{label} part {part}");
+                let result = panic::catch_unwind(AssertUnwindSafe(|| {
+                    solution.solve(part, &file_content, &params)
+                }));
+                let answer = match result {
+                    Ok(Some(Ok(answer))) => answer,
+                    Ok(Some(Err(e))) => {
+                        println!("{label}: FAIL, {e}");
+                        summary.failed += 1;
+                        continue;
+                    }
+                    Ok(None) => continue,
+                    Err(payload) => {
+                        println!(
+                            "{label}: FAIL, panicked: {}",
+                            panic_message(payload.as_ref())
+                        );
+                        summary.failed += 1;
+                        continue;
+                    }
+                };
+                match entry.answer(part) {
+                    Some(expected) if answers::is_same(expected, &answer) => {
+                        println!("{label}: pass");
+                        summary.passed += 1;
+                    }
+                    Some(expected) => {
+                        println!(
+                            "{label}: FAIL, expected{}, got{}",
+                            show(&Answer::from(expected)),
+                            show(&answer)
+                        );
+                        summary.failed += 1;
+                    }
+                    None if record => {
+                        println!("{label}: recorded{}", show(&answer));
+                        db.record(year, day, &entry.name, part, &answer);
+                        summary.recorded += 1;
+                    }
+                    None => {
+                        println!("{label}: missing, got{}", show(&answer));
+                        summary.missing += 1;
+                    }
+                }
+            }
+        }
+    }
+
+    panic::set_hook(hook);
+
+    if summary.recorded > 0 {
+        fs::write(&path, db.to_string()).map_err(|e| format!("cannot write {path}: {e}"))?;
+    }
+    println!(
+        "{} passed, {} failed, {} missing, {} recorded",
+        summary.passed, summary.failed, summary.missing, summary.recorded
+    );
+    Ok(summary.failed == 0)
+}
+
+/// Formats the answer to follow a label, art goes to the next lines.
+fn show(answer: &Answer) -> String {
+    if answer.is_multiline() {
+        format!("\n{}", answer.to_string().trim_end())
+    } else {
+        format!(" {answer}")
+    }
+}
@@ -2,6 +2,7 @@ use std::cmp::Ordering;
 use std::collections::{HashMap, VecDeque};
 use std::time::Instant;
 
+use advent::trace::{Event, Trace};
 use advent::y22d16::parse::{self, parse_id};
 use advent::y22d16::valve::Valve;
 
@@ -51,13 +52,13 @@ struct Cache {
 }
 
 impl Cache {
-    fn new(node_count: usize) -> Self {
+    fn new(node_count: usize, trace: &Trace) -> Self {
         let mut array_size = PART_COUNT;
         array_size *= (MAX_DISTANCE + 1) as usize;
         array_size *= 1 << node_count;
         array_size *= node_count;
 
-        println!("Cache size: {}", array_size);
+        trace.event("cache", &[("size", &array_size)]);
 
         Self {
             backing_array: vec![u16::MAX; array_size],
@@ -148,9 +149,15 @@ fn get_max_flow(
 }
 
 fn main() {
+    // the events are printed to stderr with --trace, like `advent run` does
+    let trace = if std::env::args().skip(1).any(|arg| arg == "--trace") {
+        Trace::new(|event: &Event| eprintln!("{event}"))
+    } else {
+        Trace::default()
+    };
     let input = include_str!("../y22d16/example.txt");
 
-    let mut input = parse::parse(&input);
+    let mut input = parse::parse(input).unwrap();
 
     let aa = parse_id("AA").unwrap().1;
 
@@ -176,7 +183,7 @@ fn main() {
     } in input
     {
         nodes.push(Node::new(v_id_map.len() as u8));
-        v_id_map.insert(v_id.clone(), v_id_map.len() as u8);
+        v_id_map.insert(v_id, v_id_map.len() as u8);
         v_rates.push(rate);
         v_edges.push(edges);
         if rate != 0 {
@@ -198,7 +205,7 @@ fn main() {
         .collect::<Vec<_>>();
 
     let start = Instant::now();
-    let mut cache = Cache::new(interesting_nodes);
+    let mut cache = Cache::new(interesting_nodes, &trace);
     for node in &mut nodes[0..interesting_nodes] {
         node.build_node(&v_edges);
     }
@@ -213,8 +220,7 @@ fn main() {
         &mut cache,
     );
 
-    println!("{}", max_flow);
-
-    let end = Instant::now();
-    println!("{:?}", end - start);
+    let elapsed = format!("{:?}", start.elapsed());
+    trace.event("max_flow", &[("elapsed", &elapsed)]);
+    println!("{max_flow}");
 }
@@ -0,0 +1,177 @@
+//! Fast-forwarding of simulations that end up repeating themselves. The simulation is run
+//! step by step until the fingerprint of its state repeats, the values of a metric after
+//! any number of steps are then extrapolated from the cycle.
+//!
+//! ```
+//! use advent::cycle::{simulate, Cycle};
+//!
+//! // the counter goes 0, 1, 2, 3, 1, 2, 3, ... and the sum grows by 6 every cycle
+//! let history = simulate(
+//!     (0, 0),
+//!     |(counter, sum)| {
+//!         *counter = *counter % 3 + 1;
+//!         *sum += *counter;
+//!     },
+//!     |&(counter, _)| counter,
+//!     |&(_, sum)| sum,
+//!     usize::MAX,
+//! );
+//! assert_eq!(history.cycle(), Some(Cycle { start: 1, length: 3 }));
+//! assert_eq!(history.at(999_999), 333_333 * 6);
+//! ```
+use std::{
+    collections::HashMap,
+    hash::Hash,
+    ops::{Add, Mul, Sub},
+};
+
+/// The states after `start + i` and `start + length + i` steps are the same for every `i`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    pub start: usize,
+    pub length: usize,
+}
+
+impl Cycle {
+    /// Returns the first step that has the same state as the given one.
+    pub fn reduce(&self, step: usize) -> usize {
+        if step < self.start {
+            step
+        } else {
+            self.start + (step - self.start) % self.length
+        }
+    }
+}
+
+/// Metric that can be extrapolated, it is expected to change by the same amount every cycle.
+pub trait Metric: Copy + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self> {
+    fn from_count(count: usize) -> Self;
+}
+
+macro_rules! impl_metric {
+    ($($t:ty),*) => {
+        $(impl Metric for $t {
+            fn from_count(count: usize) -> Self {
+                Self::try_from(count).expect("the number of cycles does not fit the metric")
+            }
+        })*
+    };
+}
+
+impl_metric!(i32, i64, i128, isize, u32, u64, u128, usize);
+
+/// Values of the metric after every step of a simulation up to the first repeated state.
+#[derive(Debug, Clone)]
+pub struct History<M> {
+    values: Vec<M>,
+    cycle: Option<Cycle>,
+}
+
+impl<M: Metric> History<M> {
+    pub fn cycle(&self) -> Option<Cycle> {
+        self.cycle
+    }
+
+    /// Number of the simulated steps.
+    pub fn steps(&self) -> usize {
+        self.values.len() - 1
+    }
+
+    /// Returns the value of the metric after the steps.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the simulation stopped at the limit before that step without finding
+    /// a cycle.
+    pub fn at(&self, step: usize) -> M {
+        if let Some(&value) = self.values.get(step) {
+            return value;
+        }
+        let cycle = self
+            .cycle
+            .unwrap_or_else(|| panic!("step {step} is beyond the simulated steps"));
+        let cycles = (step - cycle.start) / cycle.length;
+        let per_cycle = self.values[cycle.start + cycle.length] - self.values[cycle.start];
+        self.values[cycle.reduce(step)] + per_cycle * M::from_count(cycles)
+    }
+}
+
+/// Runs `step` on the state until its fingerprint repeats or `limit` steps are made,
+/// recording the metric of the initial state and of the state after every step.
+/// The simulation is expected to be deterministic, so equal fingerprints mean equal futures.
+pub fn simulate<S, K, M>(
+    mut state: S,
+    mut step: impl FnMut(&mut S),
+    mut fingerprint: impl FnMut(&S) -> K,
+    mut metric: impl FnMut(&S) -> M,
+    limit: usize,
+) -> History<M>
+where
+    K: Eq + Hash,
+    M: Metric,
+{
+    let mut seen = HashMap::new();
+    let mut values = vec![metric(&state)];
+    let mut cycle = None;
+    for steps in 0..=limit {
+        if let Some(start) = seen.insert(fingerprint(&state), steps) {
+            cycle = Some(Cycle {
+                start,
+                length: steps - start,
+            });
+            break;
+        }
+        if steps == limit {
+            break;
+        }
+        step(&mut state);
+        values.push(metric(&state));
+    }
+    History { values, cycle }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cycle() {
+        let cycle = Cycle {
+            start: 2,
+            length: 3,
+        };
+        assert_eq!(
+            (0..9).map(|step| cycle.reduce(step)).collect::<Vec<_>>(),
+            vec![0, 1, 2, 3, 4, 2, 3, 4, 2]
+        );
+    }
+
+    #[test]
+    fn test_simulate() {
+        // the values go 1, 2, 4, 8, 16 % 13 = 3, 6, 12, 11, 9, 5, 10, 7, 1
+        let history = simulate(1, |n| *n = *n * 2 % 13, |&n| n, |&n| n as u64, 100);
+        assert_eq!(
+            history.cycle(),
+            Some(Cycle {
+                start: 0,
+                length: 12
+            })
+        );
+        assert_eq!(history.at(13), 2);
+        assert_eq!(history.at(12 * 1000 + 4), 3);
+    }
+
+    #[test]
+    fn test_limit() {
+        let history = simulate(0u64, |n| *n += 1, |&n| n, |&n| n, 10);
+        assert_eq!(history.cycle(), None);
+        assert_eq!(history.steps(), 10);
+        assert_eq!(history.at(10), 10);
+    }
+
+    #[test]
+    #[should_panic(expected = "step 11 is beyond the simulated steps")]
+    fn test_beyond_limit() {
+        simulate(0u64, |n| *n += 1, |&n| n, |&n| n, 10).at(11);
+    }
+}
@@ -1,29 +1,30 @@
-use crate::reduces::Reduces;
+use crate::{
+    input::{from_str, lines, parse_blocks},
+    parsing::ParseError,
+    registry::Registry,
+    solution::{Answer, Params, Solution},
+};
 
-fn parse_elfes_calories<'a>(file_content: &'a str) -> impl Iterator<Item = u32> + 'a {
-    file_content
-        .lines()
-        .map(|line| line.trim())
-        .reduces(0, |elf_group, line| {
-            if line.len() <= 0 {
-                return false;
-            }
-            *elf_group = *elf_group + line.parse::<u32>().unwrap();
-
-            return true;
-        })
+fn parse_elfes_calories(file_content: &str) -> impl Iterator<Item = Result<u32, ParseError>> + '_ {
+    parse_blocks(file_content, lines(from_str::<u32>))
+        .map(|elf| elf.map(|calories| calories.into_iter().sum()))
 }
 
-pub fn solve_part1(file_content: &str) -> u32 {
-    parse_elfes_calories(file_content).max().unwrap_or_default()
+pub fn solve_task1(file_content: &str) -> Result<u32, ParseError> {
+    let mut max = 0;
+    for elf in parse_elfes_calories(file_content) {
+        max = max.max(elf?);
+    }
+    Ok(max)
 }
 
-pub fn solve_part2(file_content: &str) -> u32 {
+pub fn solve_task2(file_content: &str) -> Result<u32, ParseError> {
     let mut top1 = 0;
     let mut top2 = 0;
     let mut top3 = 0;
 
     for elf in parse_elfes_calories(file_content) {
+        let elf = elf?;
         if elf <= top3 {
             continue;
         }
@@ -39,7 +40,31 @@ pub fn solve_part2(file_content: &str) -> u32 {
         }
     }
 
-    return top1 + top2 + top3;
+    Ok(top1 + top2 + top3)
+}
+
+pub struct Day;
+
+impl Solution for Day {
+    fn year(&self) -> u16 {
+        2022
+    }
+    fn day(&self) -> u8 {
+        1
+    }
+    fn title(&self) -> &'static str {
+        "Calorie Counting"
+    }
+    fn part1(&self, file_content: &str, _params: &Params) -> Result<Answer, ParseError> {
+        Ok(solve_task1(file_content)?.into())
+    }
+    fn part2(&self, file_content: &str, _params: &Params) -> Result<Answer, ParseError> {
+        Ok(solve_task2(file_content)?.into())
+    }
+}
+
+pub fn register(registry: &mut Registry) {
+    registry.register(&Day);
 }
 
 #[cfg(test)]
@@ -63,12 +88,12 @@ mod tests {
 
     #[ignore]
     #[test]
-    fn test_part1() {
-        assert_eq!(solve_part1(INPUT), 24000);
+    fn test_task1() {
+        assert_eq!(solve_task1(INPUT).unwrap(), 24000);
     }
     #[ignore]
     #[test]
-    fn test_part2() {
-        assert_eq!(solve_part2(INPUT), 45000);
+    fn test_task2() {
+        assert_eq!(solve_task2(INPUT).unwrap(), 45000);
     }
 }
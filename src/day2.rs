@@ -1,3 +1,15 @@
+use crate::{
+    parsing::{parse_lines, IResult, ParseError},
+    registry::Registry,
+    solution::{Answer, Params, Solution},
+};
+use nom::{
+    branch::alt,
+    character::complete::{char, space1},
+    combinator::value,
+    sequence::separated_pair,
+};
+
 #[derive(Debug, PartialEq, Eq, Copy, Clone)]
 enum Choice {
     Rock = 1,
@@ -5,20 +17,6 @@ enum Choice {
     Scissors = 3,
 }
 
-impl From<char> for Choice {
-    fn from(value: char) -> Self {
-        match value {
-            'A' => Choice::Rock,
-            'B' => Choice::Paper,
-            'C' => Choice::Scissors,
-            'X' => Choice::Rock,
-            'Y' => Choice::Paper,
-            'Z' => Choice::Scissors,
-            _ => unreachable!(),
-        }
-    }
-}
-
 fn play(opponent: Choice, my_choice: Choice) -> Outcome {
     match (opponent, my_choice) {
         (Choice::Rock, Choice::Paper) => Outcome::Win,
@@ -31,48 +29,50 @@ fn play(opponent: Choice, my_choice: Choice) -> Outcome {
     }
 }
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
 enum Outcome {
     Loss = 0,
     Draw = 3,
     Win = 6,
 }
 
-impl From<char> for Outcome {
-    fn from(c: char) -> Self {
-        match c {
-            'X' => Outcome::Loss,
-            'Y' => Outcome::Draw,
-            'Z' => Outcome::Win,
-            _ => unreachable!(),
-        }
-    }
-}
-
 fn get_score((a, b): (Choice, Choice)) -> u32 {
     play(a, b) as u32 + b as u32
 }
 
-fn parse_char_pairs(input: &str) -> impl Iterator<Item = (char, char)> + '_ {
-    input.lines().map(|line| {
-        let mut chars = line.chars();
-        let first_char = chars.next().unwrap();
-        chars.next();
-        let second_char = chars.next().unwrap();
+/// Parses one of the three letters as the value standing at the same place.
+fn parse_letter<'a, T: Clone>(
+    letters: [char; 3],
+    values: [T; 3],
+) -> impl FnMut(&'a str) -> IResult<'a, T> {
+    let [a, b, c] = values;
+    alt((
+        value(a, char(letters[0])),
+        value(b, char(letters[1])),
+        value(c, char(letters[2])),
+    ))
+}
 
-        (first_char, second_char)
-    })
+/// Parses the rounds of the strategy guide, `second` is what `X`, `Y` and `Z` stand for.
+fn parse_rounds<T: Clone>(input: &str, second: [T; 3]) -> Result<Vec<(Choice, T)>, ParseError> {
+    let opponent = parse_letter(
+        ['A', 'B', 'C'],
+        [Choice::Rock, Choice::Paper, Choice::Scissors],
+    );
+    let round = separated_pair(opponent, space1, parse_letter(['X', 'Y', 'Z'], second));
+    parse_lines(input, round).collect()
 }
 
-pub fn solve_part1(file_content: &str) -> u32 {
-    parse_char_pairs(file_content)
-        .map(|(a, b)| (a.into(), b.into()))
-        .map(get_score)
-        .sum::<u32>()
+pub fn solve_task1(file_content: &str) -> Result<u32, ParseError> {
+    let rounds = parse_rounds(
+        file_content,
+        [Choice::Rock, Choice::Paper, Choice::Scissors],
+    )?;
+    Ok(rounds.into_iter().map(get_score).sum())
 }
 
 fn restore_your_move(opponent: Choice, outcome: Outcome) -> Choice {
-    for my_choice in vec![Choice::Rock, Choice::Paper, Choice::Scissors] {
+    for my_choice in [Choice::Rock, Choice::Paper, Choice::Scissors] {
         if play(opponent, my_choice) == outcome {
             return my_choice;
         }
@@ -80,12 +80,37 @@ fn restore_your_move(opponent: Choice, outcome: Outcome) -> Choice {
     unreachable!("No move found")
 }
 
-pub fn solve_part2(file_content: &str) -> u32 {
-    parse_char_pairs(file_content)
-        .map(|(a, b)| (a.into(), b.into()))
+pub fn solve_task2(file_content: &str) -> Result<u32, ParseError> {
+    let rounds = parse_rounds(file_content, [Outcome::Loss, Outcome::Draw, Outcome::Win])?;
+    Ok(rounds
+        .into_iter()
         .map(|(opponent, outcome)| (opponent, restore_your_move(opponent, outcome)))
         .map(get_score)
-        .sum()
+        .sum())
+}
+
+pub struct Day;
+
+impl Solution for Day {
+    fn year(&self) -> u16 {
+        2022
+    }
+    fn day(&self) -> u8 {
+        2
+    }
+    fn title(&self) -> &'static str {
+        "Rock Paper Scissors"
+    }
+    fn part1(&self, file_content: &str, _params: &Params) -> Result<Answer, ParseError> {
+        Ok(solve_task1(file_content)?.into())
+    }
+    fn part2(&self, file_content: &str, _params: &Params) -> Result<Answer, ParseError> {
+        Ok(solve_task2(file_content)?.into())
+    }
+}
+
+pub fn register(registry: &mut Registry) {
+    registry.register(&Day);
 }
 
 #[cfg(test)]
@@ -96,12 +121,20 @@ mod tests {
 
     #[ignore]
     #[test]
-    fn test_part1() {
-        assert_eq!(solve_part1(INPUT), 15);
+    fn test_task1() {
+        assert_eq!(solve_task1(INPUT), Ok(15));
     }
     #[ignore]
     #[test]
-    fn test_part2() {
-        assert_eq!(solve_part2(INPUT), 12);
+    fn test_task2() {
+        assert_eq!(solve_task2(INPUT), Ok(12));
+    }
+
+    #[test]
+    fn test_invalid_round() {
+        assert_eq!(
+            solve_task1("A Y\nD X").unwrap_err().to_string(),
+            "line 2, column 1: expected 'A' or 'B' or 'C', found \"D X\""
+        );
     }
 }
@@ -1,5 +1,12 @@
-fn parse_lines(input: &str) -> impl Iterator<Item = Vec<char>> + '_ {
-    return input.lines().map(|line| line.chars().collect::<Vec<_>>());
+use crate::{
+    parsing::{parse_lines, Error, Expected, ParseError},
+    registry::Registry,
+    solution::{Answer, Params, Solution},
+};
+use nom::{character::complete::alpha1, combinator::map};
+
+fn parse_rucksacks(input: &str) -> Result<Vec<Vec<char>>, ParseError> {
+    parse_lines(input, map(alpha1, |line: &str| line.chars().collect())).collect()
 }
 
 fn get_value(char: char) -> usize {
@@ -40,32 +47,57 @@ fn calculate_score(parts: &[&[char]]) -> u32 {
     score as u32
 }
 
-pub fn solve_part1(file_content: &str) -> u32 {
+pub fn solve_task1(file_content: &str) -> Result<u32, ParseError> {
     let mut res = 0;
-    for line in parse_lines(file_content) {
+    for line in parse_rucksacks(file_content)? {
         let compartments = [&line[..line.len() / 2], &line[line.len() / 2..]];
         res += calculate_score(&compartments);
     }
-    res
+    Ok(res)
 }
 
-pub fn solve_part2(file_content: &str) -> u32 {
+pub fn solve_task2(file_content: &str) -> Result<u32, ParseError> {
     let mut score = 0;
 
-    let mut lines = parse_lines(file_content);
-    loop {
-        let first = lines.next();
-        if first.is_none() {
-            break;
-        }
-        let first = first.unwrap();
-        let second = lines.next().unwrap();
-        let third = lines.next().unwrap();
-        let group: [&[char]; 3] = [&first, &second, &third];
+    let rucksacks = parse_rucksacks(file_content)?;
+    for group in rucksacks.chunks(3) {
+        let [first, second, third] = group else {
+            // the last group of the elves is not full
+            let end = &file_content[file_content.len()..];
+            return Err(ParseError::new(
+                file_content,
+                Error::new(end, Expected::Context("rucksack")),
+            ));
+        };
+        let group: [&[char]; 3] = [first, second, third];
         score += calculate_score(&group);
     }
 
-    score
+    Ok(score)
+}
+
+pub struct Day;
+
+impl Solution for Day {
+    fn year(&self) -> u16 {
+        2022
+    }
+    fn day(&self) -> u8 {
+        3
+    }
+    fn title(&self) -> &'static str {
+        "Rucksack Reorganization"
+    }
+    fn part1(&self, file_content: &str, _params: &Params) -> Result<Answer, ParseError> {
+        Ok(solve_task1(file_content)?.into())
+    }
+    fn part2(&self, file_content: &str, _params: &Params) -> Result<Answer, ParseError> {
+        Ok(solve_task2(file_content)?.into())
+    }
+}
+
+pub fn register(registry: &mut Registry) {
+    registry.register(&Day);
 }
 
 #[cfg(test)]
@@ -93,13 +125,27 @@ CrZsJsPPZsGzwwsLwLmpwMDw";
 
     #[ignore]
     #[test]
-    fn test_part1() {
-        assert_eq!(solve_part1(INPUT), 157);
+    fn test_task1() {
+        assert_eq!(solve_task1(INPUT), Ok(157));
     }
 
     #[ignore]
     #[test]
-    fn test_part2() {
-        assert_eq!(solve_part2(INPUT), 70);
+    fn test_task2() {
+        assert_eq!(solve_task2(INPUT), Ok(70));
+    }
+
+    #[test]
+    fn test_invalid_rucksacks() {
+        assert_eq!(
+            solve_task1("vJrwpWtwJgWr\nhcs FMM")
+                .unwrap_err()
+                .to_string(),
+            "line 2, column 4: expected end of line, found \" FMM\""
+        );
+        assert_eq!(
+            solve_task2("vJrwpWtwJgWr\nhcsFMM").unwrap_err().to_string(),
+            "line 2, column 7: expected rucksack, found end of line"
+        );
     }
 }
@@ -0,0 +1,263 @@
+//! Rectangular map of cells stored in one vector row by row, the cells are addressed by
+//! `(row, col)` positions.
+use crate::parsing::{Error, Expected, ParseError};
+use std::ops::{Index, IndexMut};
+
+pub type Pos = (usize, usize);
+
+const NEIGHBOURS4: [(isize, isize); 4] = [(-1, 0), (0, 1), (1, 0), (0, -1)];
+const NEIGHBOURS8: [(isize, isize); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+    (1, 0),
+    (1, -1),
+    (0, -1),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    rows: usize,
+    cols: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(rows: usize, cols: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            rows,
+            cols,
+            cells: vec![value; rows * cols],
+        }
+    }
+
+    /// Parses a map of characters where every line is a row. `cell` converts a character
+    /// at the position, `None` reports it as invalid.
+    pub fn parse(
+        content: &str,
+        mut cell: impl FnMut(Pos, char) -> Option<T>,
+    ) -> Result<Self, ParseError> {
+        let mut cells = Vec::with_capacity(content.len());
+        let mut cols = None;
+        let mut rows = 0;
+        for (row, line) in content.lines().enumerate() {
+            let mut col = 0;
+            for (offset, ch) in line.char_indices() {
+                let value = cell((row, col), ch).ok_or_else(|| {
+                    ParseError::new(
+                        content,
+                        Error::new(&line[offset..], Expected::Context("map cell")),
+                    )
+                })?;
+                cells.push(value);
+                col += 1;
+            }
+            let expected = *cols.get_or_insert(col);
+            if expected != col {
+                let offset = line
+                    .char_indices()
+                    .nth(expected)
+                    .map_or(line.len(), |(offset, _)| offset);
+                return Err(ParseError::new(
+                    content,
+                    Error::new(&line[offset..], Expected::Context("row of the same width")),
+                ));
+            }
+            rows += 1;
+        }
+        Ok(Self {
+            rows,
+            cols: cols.unwrap_or_default(),
+            cells,
+        })
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn contains(&self, (row, col): Pos) -> bool {
+        row < self.rows && col < self.cols
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        self.contains(pos)
+            .then(|| &self.cells[pos.0 * self.cols + pos.1])
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        if self.contains(pos) {
+            Some(&mut self.cells[pos.0 * self.cols + pos.1])
+        } else {
+            None
+        }
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.cols..(row + 1) * self.cols]
+    }
+
+    pub fn row_mut(&mut self, row: usize) -> &mut [T] {
+        &mut self.cells[row * self.cols..(row + 1) * self.cols]
+    }
+
+    /// Returns the cells of the column from the top to the bottom.
+    pub fn column(&self, col: usize) -> impl DoubleEndedIterator<Item = &T> + ExactSizeIterator {
+        assert!(col < self.cols, "column {col} is out of the grid");
+        self.cells[col..].iter().step_by(self.cols)
+    }
+
+    /// Returns the positions row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let cols = self.cols;
+        (0..self.rows).flat_map(move |row| (0..cols).map(move |col| (row, col)))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(&self.cells)
+    }
+
+    pub fn position(&self, predicate: impl Fn(&T) -> bool) -> Option<Pos> {
+        self.iter()
+            .find(|(_, cell)| predicate(cell))
+            .map(|(pos, _)| pos)
+    }
+
+    /// Returns the neighbours that share a side with the position.
+    pub fn neighbours4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        self.neighbours(pos, &NEIGHBOURS4)
+    }
+
+    /// Returns the neighbours that share a side or a corner with the position.
+    pub fn neighbours8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        self.neighbours(pos, &NEIGHBOURS8)
+    }
+
+    fn neighbours(
+        &self,
+        (row, col): Pos,
+        offsets: &'static [(isize, isize)],
+    ) -> impl Iterator<Item = Pos> + '_ {
+        offsets.iter().filter_map(move |&(dr, dc)| {
+            let pos = (row.checked_add_signed(dr)?, col.checked_add_signed(dc)?);
+            self.contains(pos).then_some(pos)
+        })
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            rows: self.rows,
+            cols: self.cols,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &T {
+        self.get(pos)
+            .unwrap_or_else(|| panic!("{pos:?} is out of the grid"))
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut T {
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("{pos:?} is out of the grid"))
+    }
+}
+
+impl<T: std::fmt::Display> std::fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in 0..self.rows {
+            for cell in self.row(row) {
+                write!(f, "{cell}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAP: &str = "123\n456";
+
+    fn digits(content: &str) -> Result<Grid<u32>, ParseError> {
+        Grid::parse(content, |_, ch| ch.to_digit(10))
+    }
+
+    #[test]
+    fn test_parse() {
+        let grid = digits(MAP).unwrap();
+        assert_eq!((grid.rows(), grid.cols()), (2, 3));
+        assert_eq!(grid[(1, 0)], 4);
+        assert_eq!(grid.get((2, 0)), None);
+        assert_eq!(grid.row(1), &[4, 5, 6]);
+        assert_eq!(grid.column(2).copied().collect::<Vec<_>>(), vec![3, 6]);
+        assert_eq!(
+            grid.column(1).rev().copied().collect::<Vec<_>>(),
+            vec![5, 2]
+        );
+        assert_eq!(grid.position(|&cell| cell == 5), Some((1, 1)));
+        assert_eq!(grid.to_string(), "123\n456\n");
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            digits("123\n4x6").unwrap_err().to_string(),
+            "line 2, column 2: expected map cell, found \"x6\""
+        );
+        assert_eq!(
+            digits("123\n4567").unwrap_err().to_string(),
+            "line 2, column 4: expected row of the same width, found \"7\""
+        );
+        assert_eq!(
+            digits("123\n45").unwrap_err().to_string(),
+            "line 2, column 3: expected row of the same width, found end of line"
+        );
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid = digits(MAP).unwrap();
+        assert_eq!(
+            grid.neighbours4((0, 0)).collect::<Vec<_>>(),
+            vec![(0, 1), (1, 0)]
+        );
+        assert_eq!(
+            grid.neighbours4((1, 1)).collect::<Vec<_>>(),
+            vec![(0, 1), (1, 2), (1, 0)]
+        );
+        assert_eq!(
+            grid.neighbours8((0, 2)).collect::<Vec<_>>(),
+            vec![(1, 2), (1, 1), (0, 1)]
+        );
+    }
+
+    #[test]
+    fn test_mutation() {
+        let mut grid = Grid::new(2, 2, '.');
+        grid[(0, 1)] = '#';
+        grid.row_mut(1)[0] = '#';
+        assert_eq!(grid.to_string(), ".#\n#.\n");
+        assert_eq!(
+            grid.map(|&c| c == '#').iter().filter(|(_, &c)| c).count(),
+            2
+        );
+    }
+}
@@ -0,0 +1,203 @@
+//! Preparation of the puzzle inputs before they are given to the solutions and helpers to
+//! split them into parts.
+use std::{borrow::Cow, str::FromStr};
+
+use nom::{character::complete::newline, combinator::cut, multi::separated_list1};
+
+use crate::{
+    parsing::{parse_all, Error, Expected, IResult, ParseError},
+    reduces::Reduces,
+};
+
+/// How the input of a puzzle is normalized. The byte order mark, the carriage returns of
+/// CRLF line endings and the newlines at the end of the input are always removed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Normalization {
+    /// Removes the whitespace at the end of every line. It is turned off for the formats
+    /// where the whitespace is significant, like the crate diagram of 2022 day 5.
+    pub trim_lines: bool,
+}
+
+impl Default for Normalization {
+    fn default() -> Self {
+        Self { trim_lines: true }
+    }
+}
+
+/// Normalizes the input, it is borrowed if there is nothing to change.
+pub fn normalize(content: &str, normalization: Normalization) -> Cow<'_, str> {
+    let content = content.strip_prefix('\u{feff}').unwrap_or(content);
+    let content = content.trim_end_matches(['\r', '\n']);
+    let is_normalized = !content.contains('\r')
+        && (!normalization.trim_lines
+            || content
+                .lines()
+                .all(|line| !line.ends_with(char::is_whitespace)));
+    if is_normalized {
+        return Cow::Borrowed(content);
+    }
+
+    let mut res = String::with_capacity(content.len());
+    for line in content.lines() {
+        let line = line.strip_suffix('\r').unwrap_or(line);
+        let line = if normalization.trim_lines {
+            line.trim_end()
+        } else {
+            line
+        };
+        res.push_str(line);
+        res.push('\n');
+    }
+    let len = res.trim_end_matches('\n').len();
+    res.truncate(len);
+    Cow::Owned(res)
+}
+
+/// Splits the content into blocks of lines separated by blank lines. The blocks are slices
+/// of the content, so the errors of parsing them can be reported relative to it.
+pub fn blocks(content: &str) -> impl Iterator<Item = &str> + '_ {
+    content
+        .lines()
+        .reduces(None, |block: &mut Option<&str>, line| {
+            if line.trim().is_empty() {
+                return false;
+            }
+            *block = Some(match *block {
+                Some(block) => {
+                    let start = block.as_ptr() as usize - content.as_ptr() as usize;
+                    let end = line.as_ptr() as usize - content.as_ptr() as usize + line.len();
+                    &content[start..end]
+                }
+                None => line,
+            });
+            true
+        })
+        .flatten()
+}
+
+/// Parses every block of `file_content` with `parser`, see [`blocks`].
+pub fn parse_blocks<'a, T: 'a>(
+    file_content: &'a str,
+    mut parser: impl FnMut(&'a str) -> IResult<'a, T> + 'a,
+) -> impl Iterator<Item = Result<T, ParseError>> + 'a {
+    blocks(file_content).map(move |block| parse_all(file_content, block, &mut parser))
+}
+
+/// Parses every line of a block with `parser`. Unlike [`separated_list1`] it does not
+/// backtrack, so the error of a malformed line is reported at that line.
+///
+/// [`separated_list1`]: nom::multi::separated_list1
+pub fn lines<'a, T>(
+    parser: impl FnMut(&'a str) -> IResult<'a, T>,
+) -> impl FnMut(&'a str) -> IResult<'a, Vec<T>> {
+    separated_list1(newline, cut(parser))
+}
+
+/// Parses the rest of the line with [`FromStr`], ignoring the spaces around the value.
+pub fn from_str<T: FromStr>(input: &str) -> IResult<'_, T> {
+    let end = input.find('\n').unwrap_or(input.len());
+    let (line, rest) = input.split_at(end);
+    let value = line.trim();
+    match value.parse() {
+        Ok(value) => Ok((rest, value)),
+        Err(_) => {
+            let start = &line[line.len() - line.trim_start().len()..];
+            let name = std::any::type_name::<T>().rsplit("::").next().unwrap();
+            Err(nom::Err::Error(Error::new(start, Expected::Context(name))))
+        }
+    }
+}
+
+/// Extracts the integers from the text, skipping everything else. A minus right before
+/// the digits is taken as the sign unless the type cannot be negative.
+pub fn numbers<T: FromStr>(text: &str) -> impl Iterator<Item = T> + '_ {
+    let mut rest = text;
+    std::iter::from_fn(move || loop {
+        let start = rest.find(|c: char| c.is_ascii_digit())?;
+        let len = rest[start..]
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(rest.len() - start);
+        let signed = start
+            .checked_sub(1)
+            .filter(|&sign| rest.as_bytes()[sign] == b'-')
+            .map(|sign| &rest[sign..start + len]);
+        let digits = &rest[start..start + len];
+        rest = &rest[start + len..];
+        let value = signed
+            .and_then(|signed| signed.parse().ok())
+            .or_else(|| digits.parse().ok());
+        if value.is_some() {
+            return value;
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEEP_LINES: Normalization = Normalization { trim_lines: false };
+
+    #[test]
+    fn test_normalized() {
+        let content = "addx 15\naddx -11";
+        assert!(matches!(
+            normalize(content, Normalization::default()),
+            Cow::Borrowed("addx 15\naddx -11")
+        ));
+        assert!(matches!(
+            normalize("addx 15\n\n", Normalization::default()),
+            Cow::Borrowed("addx 15")
+        ));
+    }
+
+    #[test]
+    fn test_normalize() {
+        let content = "\u{feff}    [D] \r\n[N] [C]    \r\n\r\nmove 1 from 2 to 1\t\r\n\r\n";
+        assert_eq!(
+            normalize(content, Normalization::default()),
+            "    [D]\n[N] [C]\n\nmove 1 from 2 to 1"
+        );
+        assert_eq!(
+            normalize(content, KEEP_LINES),
+            "    [D] \n[N] [C]    \n\nmove 1 from 2 to 1\t"
+        );
+    }
+
+    #[test]
+    fn test_blocks() {
+        let content = "1\n2\n\n3\n  \n\n4\n5\n";
+        assert_eq!(
+            blocks(content).collect::<Vec<_>>(),
+            vec!["1\n2", "3", "4\n5"]
+        );
+        assert_eq!(blocks("").count(), 0);
+    }
+
+    #[test]
+    fn test_parse_blocks() {
+        let content = "1\n 2\n\n3\n\n4\nfive";
+        let mut blocks = parse_blocks(content, lines(from_str::<u32>));
+        assert_eq!(blocks.next(), Some(Ok(vec![1, 2])));
+        assert_eq!(blocks.next(), Some(Ok(vec![3])));
+        assert_eq!(
+            blocks.next().unwrap().unwrap_err().to_string(),
+            "line 7, column 1: expected u32, found \"five\""
+        );
+    }
+
+    #[test]
+    fn test_numbers() {
+        let text = "Sensor at x=2, y=-18: closest beacon is at x=-2, y=15";
+        assert_eq!(
+            numbers::<i32>(text).collect::<Vec<_>>(),
+            vec![2, -18, -2, 15]
+        );
+        assert_eq!(numbers::<u32>(text).collect::<Vec<_>>(), vec![2, 18, 2, 15]);
+        assert_eq!(
+            numbers::<u8>("move 1 from 300 to 2").collect::<Vec<_>>(),
+            vec![1, 2]
+        );
+        assert_eq!(numbers::<u8>("no numbers").next(), None);
+    }
+}
@@ -0,0 +1,296 @@
+//! Sets of integers stored as sorted disjoint inclusive ranges.
+//!
+//! ```
+//! use advent::interval::IntervalSet;
+//!
+//! let mut set = IntervalSet::from_iter([1..=3, 8..=9, 4..=5]);
+//! set.remove(2..=2);
+//! assert_eq!(set.to_string(), "{1, 3..=5, 8..=9}");
+//! assert_eq!(set.len(), 6);
+//! assert_eq!(set.gaps().collect::<Vec<_>>(), vec![2..=2, 6..=7]);
+//! ```
+use std::ops::RangeInclusive;
+
+/// Integer usable as a bound of the intervals.
+pub trait Bound: Copy + Ord + std::fmt::Debug {
+    /// Returns the next value, it is called only for values less than some other one.
+    fn succ(self) -> Self;
+    /// Returns the previous value, it is called only for values greater than some other one.
+    fn pred(self) -> Self;
+    /// Number of the values in `start..=end`, `start` is not greater than `end`.
+    fn count(start: Self, end: Self) -> u64;
+}
+
+macro_rules! impl_bound {
+    ($($t:ty),*) => {
+        $(impl Bound for $t {
+            fn succ(self) -> Self {
+                self + 1
+            }
+
+            fn pred(self) -> Self {
+                self - 1
+            }
+
+            fn count(start: Self, end: Self) -> u64 {
+                (end as i128 - start as i128 + 1) as u64
+            }
+        })*
+    };
+}
+
+impl_bound!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct IntervalSet<T> {
+    /// Sorted intervals `(start, end)`, there is a gap of at least one value between them.
+    intervals: Vec<(T, T)>,
+}
+
+impl<T: Bound> IntervalSet<T> {
+    pub fn new() -> Self {
+        Self {
+            intervals: Vec::new(),
+        }
+    }
+
+    /// Adds the values of the range, merging it with the intervals it overlaps or touches.
+    pub fn insert(&mut self, range: RangeInclusive<T>) {
+        let (start, end) = range.into_inner();
+        if start > end {
+            return;
+        }
+        let first = self
+            .intervals
+            .partition_point(|&(_, e)| e < start && e.succ() < start);
+        let last = self
+            .intervals
+            .partition_point(|&(s, _)| s <= end || s.pred() <= end);
+        let merged = match self.intervals.get(first..last) {
+            Some([(s, _), .., (_, e)]) | Some([(s, e)]) => (start.min(*s), end.max(*e)),
+            _ => (start, end),
+        };
+        self.intervals.splice(first..last, [merged]);
+    }
+
+    /// Removes the values of the range, splitting the intervals it cuts.
+    pub fn remove(&mut self, range: RangeInclusive<T>) {
+        let (start, end) = range.into_inner();
+        if start > end {
+            return;
+        }
+        let first = self.intervals.partition_point(|&(_, e)| e < start);
+        let last = self.intervals.partition_point(|&(s, _)| s <= end);
+        if first == last {
+            return;
+        }
+        let (first_start, _) = self.intervals[first];
+        let (_, last_end) = self.intervals[last - 1];
+        let rest = [
+            (first_start < start).then(|| (first_start, start.pred())),
+            (last_end > end).then(|| (end.succ(), last_end)),
+        ];
+        self.intervals
+            .splice(first..last, rest.into_iter().flatten());
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        self.contains_range(value..=value)
+    }
+
+    /// Checks whether all the values of the range are in the set, the empty range is always
+    /// contained.
+    pub fn contains_range(&self, range: RangeInclusive<T>) -> bool {
+        let (start, end) = range.into_inner();
+        if start > end {
+            return true;
+        }
+        let ind = self.intervals.partition_point(|&(_, e)| e < start);
+        self.intervals
+            .get(ind)
+            .is_some_and(|&(s, e)| s <= start && end <= e)
+    }
+
+    pub fn is_superset(&self, other: &Self) -> bool {
+        other
+            .intervals
+            .iter()
+            .all(|&(s, e)| self.contains_range(s..=e))
+    }
+
+    pub fn is_subset(&self, other: &Self) -> bool {
+        other.is_superset(self)
+    }
+
+    pub fn is_disjoint(&self, other: &Self) -> bool {
+        self.overlapping(other).next().is_none()
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let mut res = self.clone();
+        for &(s, e) in &other.intervals {
+            res.insert(s..=e);
+        }
+        res
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        Self {
+            intervals: self.overlapping(other).collect(),
+        }
+    }
+
+    /// Returns the values within the bounds that are not in the set.
+    pub fn complement(&self, bounds: RangeInclusive<T>) -> Self {
+        let mut res = Self::from(bounds);
+        for &(s, e) in &self.intervals {
+            res.remove(s..=e);
+        }
+        res
+    }
+
+    /// Number of the values in the set.
+    pub fn len(&self) -> u64 {
+        self.intervals.iter().map(|&(s, e)| T::count(s, e)).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    pub fn ranges(&self) -> impl DoubleEndedIterator<Item = RangeInclusive<T>> + '_ {
+        self.intervals.iter().map(|&(s, e)| s..=e)
+    }
+
+    /// Returns the missing ranges between the smallest and the largest values of the set.
+    pub fn gaps(&self) -> impl Iterator<Item = RangeInclusive<T>> + '_ {
+        self.intervals
+            .windows(2)
+            .map(|w| w[0].1.succ()..=w[1].0.pred())
+    }
+
+    /// Returns the intersections of the intervals of the sets in order.
+    fn overlapping<'a>(&'a self, other: &'a Self) -> impl Iterator<Item = (T, T)> + 'a {
+        let (mut i, mut j) = (0, 0);
+        std::iter::from_fn(move || loop {
+            let (&(s1, e1), &(s2, e2)) = (self.intervals.get(i)?, other.intervals.get(j)?);
+            if e1 < e2 {
+                i += 1;
+            } else {
+                j += 1;
+            }
+            if s1.max(s2) <= e1.min(e2) {
+                return Some((s1.max(s2), e1.min(e2)));
+            }
+        })
+    }
+}
+
+impl<T: Bound> From<RangeInclusive<T>> for IntervalSet<T> {
+    fn from(range: RangeInclusive<T>) -> Self {
+        let mut res = Self::new();
+        res.insert(range);
+        res
+    }
+}
+
+impl<T: Bound> FromIterator<RangeInclusive<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = RangeInclusive<T>>>(iter: I) -> Self {
+        let mut res = Self::new();
+        res.extend(iter);
+        res
+    }
+}
+
+impl<T: Bound> Extend<RangeInclusive<T>> for IntervalSet<T> {
+    fn extend<I: IntoIterator<Item = RangeInclusive<T>>>(&mut self, iter: I) {
+        for range in iter {
+            self.insert(range);
+        }
+    }
+}
+
+impl<T: Bound + std::fmt::Display> std::fmt::Display for IntervalSet<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{{")?;
+        for (ind, &(s, e)) in self.intervals.iter().enumerate() {
+            if ind > 0 {
+                write!(f, ", ")?;
+            }
+            if s == e {
+                write!(f, "{s}")?;
+            } else {
+                write!(f, "{s}..={e}")?;
+            }
+        }
+        write!(f, "}}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(ranges: &[RangeInclusive<i32>]) -> IntervalSet<i32> {
+        ranges.iter().cloned().collect()
+    }
+
+    #[test]
+    fn test_insert() {
+        let mut s = set(&[10..=12, 1..=2, 5..=6]);
+        assert_eq!(s.to_string(), "{1..=2, 5..=6, 10..=12}");
+        s.insert(3..=4);
+        assert_eq!(s.to_string(), "{1..=6, 10..=12}");
+        s.insert(8..=8);
+        s.insert(0..=0);
+        s.insert(7..=11);
+        assert_eq!(s.to_string(), "{0..=12}");
+        #[allow(clippy::reversed_empty_ranges)]
+        s.insert(20..=15);
+        assert_eq!(s.len(), 13);
+        let mut s = IntervalSet::from(u8::MAX - 1..=u8::MAX);
+        s.insert(0..=u8::MAX - 2);
+        assert_eq!(s.len(), 256);
+    }
+
+    #[test]
+    fn test_remove() {
+        let mut s = set(&[0..=10, 20..=30]);
+        s.remove(5..=5);
+        assert_eq!(s.to_string(), "{0..=4, 6..=10, 20..=30}");
+        s.remove(8..=25);
+        assert_eq!(s.to_string(), "{0..=4, 6..=7, 26..=30}");
+        s.remove(-5..=0);
+        s.remove(30..=40);
+        s.remove(12..=14);
+        assert_eq!(s.to_string(), "{1..=4, 6..=7, 26..=29}");
+        s.remove(i32::MIN..=i32::MAX);
+        assert!(s.is_empty());
+    }
+
+    #[test]
+    fn test_queries() {
+        let s = set(&[0..=4, 10..=14]);
+        assert!(s.contains(4));
+        assert!(!s.contains(5));
+        assert!(s.contains_range(11..=14));
+        assert!(!s.contains_range(3..=10));
+        assert!(s.is_superset(&set(&[1..=2, 12..=12])));
+        assert!(set(&[1..=2]).is_subset(&s));
+        assert!(!s.is_superset(&set(&[1..=2, 15..=15])));
+        assert!(s.is_disjoint(&set(&[5..=9, 15..=20])));
+        assert!(!s.is_disjoint(&set(&[5..=10])));
+        assert_eq!(s.gaps().collect::<Vec<_>>(), vec![5..=9]);
+    }
+
+    #[test]
+    fn test_set_operations() {
+        let a = set(&[0..=4, 10..=14]);
+        let b = set(&[3..=11, 20..=21]);
+        assert_eq!(a.union(&b).to_string(), "{0..=14, 20..=21}");
+        assert_eq!(a.intersection(&b).to_string(), "{3..=4, 10..=11}");
+        assert_eq!(a.complement(-2..=12).to_string(), "{-2..=-1, 5..=9}");
+        assert_eq!(IntervalSet::new().complement(1..=3), set(&[1..=3]));
+        assert_eq!(a.complement(5..=9).complement(5..=9), IntervalSet::new());
+    }
+}
@@ -1,7 +1,23 @@
+pub mod animation;
+pub mod answers;
+pub mod cycle;
 pub mod day1;
 pub mod day2;
 pub mod day3;
+pub mod grid;
+pub mod input;
+pub mod interval;
+pub mod math;
+pub mod ocr;
+pub mod parsing;
+pub mod point;
 pub mod reduces;
+pub mod registry;
+pub mod render;
+pub mod search;
+pub mod solution;
+pub mod trace;
+pub mod vm;
 pub mod y15d01;
 pub mod y15d02;
 pub mod y15d03;
@@ -24,3 +40,35 @@ pub mod y22d16;
 pub mod y22d17;
 pub mod y22d18;
 pub mod y22d19;
+
+use registry::Registry;
+
+pub fn registry() -> Registry {
+    let mut registry = Registry::default();
+    day1::register(&mut registry);
+    day2::register(&mut registry);
+    day3::register(&mut registry);
+    y15d01::register(&mut registry);
+    y15d02::register(&mut registry);
+    y15d03::register(&mut registry);
+    y15d04::register(&mut registry);
+    y20d01::register(&mut registry);
+    y20d02::register(&mut registry);
+    y22d04::register(&mut registry);
+    y22d05::register(&mut registry);
+    y22d06::register(&mut registry);
+    y22d07::register(&mut registry);
+    y22d08::register(&mut registry);
+    y22d09::register(&mut registry);
+    y22d10::register(&mut registry);
+    y22d11::register(&mut registry);
+    y22d12::register(&mut registry);
+    y22d13::register(&mut registry);
+    y22d14::register(&mut registry);
+    y22d15::register(&mut registry);
+    y22d16::register(&mut registry);
+    y22d17::register(&mut registry);
+    y22d18::register(&mut registry);
+    y22d19::register(&mut registry);
+    registry
+}
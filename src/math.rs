@@ -0,0 +1,293 @@
+//! Number theory helpers: divisibility, modular inverses, systems of congruences and
+//! integers modulo a number known at runtime.
+//!
+//! ```
+//! use advent::math::{crt, lcm, ModInt};
+//!
+//! assert_eq!(lcm(4u64, 6), 12);
+//! // x = 2 (mod 3), x = 3 (mod 5), x = 2 (mod 7)
+//! assert_eq!(crt([(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+//! assert_eq!(ModInt::new(3, 7).pow(6).value(), 1);
+//! ```
+use std::ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, Rem, Sub, SubAssign};
+
+/// Primitive integer usable with [`gcd`] and [`lcm`].
+pub trait Integer:
+    Copy
+    + Ord
+    + std::fmt::Debug
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Rem<Output = Self>
+{
+    const ZERO: Self;
+
+    fn abs(self) -> Self;
+}
+
+macro_rules! impl_integer {
+    (signed $($t:ty),*) => {
+        $(impl Integer for $t {
+            const ZERO: Self = 0;
+
+            fn abs(self) -> Self {
+                <$t>::abs(self)
+            }
+        })*
+    };
+    (unsigned $($t:ty),*) => {
+        $(impl Integer for $t {
+            const ZERO: Self = 0;
+
+            fn abs(self) -> Self {
+                self
+            }
+        })*
+    };
+}
+
+impl_integer!(signed i8, i16, i32, i64, i128, isize);
+impl_integer!(unsigned u8, u16, u32, u64, u128, usize);
+
+/// Greatest common divisor, it is never negative and `gcd(0, 0)` is 0.
+pub fn gcd<T: Integer>(a: T, b: T) -> T {
+    let (mut a, mut b) = (a.abs(), b.abs());
+    while b != T::ZERO {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// Least common multiple, it is never negative and is 0 when any of the numbers is.
+pub fn lcm<T: Integer>(a: T, b: T) -> T {
+    if a == T::ZERO || b == T::ZERO {
+        return T::ZERO;
+    }
+    (a / gcd(a, b) * b).abs()
+}
+
+/// Returns `(g, x, y)` such that `a * x + b * y = g` where `g` is `gcd(a, b)`.
+pub fn ext_gcd(a: i64, b: i64) -> (i64, i64, i64) {
+    let (g, x, y) = ext_gcd_wide(a as i128, b as i128);
+    (g as i64, x as i64, y as i64)
+}
+
+fn ext_gcd_wide(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+    while r != 0 {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+    if old_r < 0 {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// Returns `x` in `0..modulus` such that `a * x = 1 (mod modulus)`, if `a` and the modulus
+/// are coprime.
+pub fn mod_inverse(a: i64, modulus: i64) -> Option<i64> {
+    mod_inverse_wide(a as i128, modulus as i128).map(|x| x as i64)
+}
+
+fn mod_inverse_wide(a: i128, modulus: i128) -> Option<i128> {
+    let (g, x, _) = ext_gcd_wide(a, modulus);
+    (g == 1).then(|| x.rem_euclid(modulus))
+}
+
+/// Solves the system of congruences `x = residue (mod modulus)` given as pairs
+/// `(residue, modulus)`. The moduli need not be coprime. Returns the smallest non-negative
+/// solution together with the lcm of the moduli, or `None` if the congruences contradict.
+///
+/// # Panics
+///
+/// Panics if a modulus is not positive or the lcm of the moduli does not fit `i64`.
+pub fn crt(congruences: impl IntoIterator<Item = (i64, i64)>) -> Option<(i64, i64)> {
+    let (mut residue, mut modulus) = (0i128, 1i128);
+    for (r, m) in congruences {
+        assert!(m > 0, "the modulus {m} is not positive");
+        let (r, m) = ((r as i128).rem_euclid(m as i128), m as i128);
+        let g = gcd(modulus, m);
+        if (r - residue) % g != 0 {
+            return None;
+        }
+        // residue + modulus * k = r (mod m), solved for k modulo m / g
+        let step = m / g;
+        let k = (r - residue) / g * mod_inverse_wide(modulus / g, step).unwrap() % step;
+        residue = (residue + modulus * k).rem_euclid(modulus * step);
+        modulus *= step;
+        assert!(
+            modulus <= i64::MAX as i128,
+            "the lcm of the moduli overflows"
+        );
+    }
+    Some((residue as i64, modulus as i64))
+}
+
+/// Integer modulo a positive number. Operations on two values expect the same modulus.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ModInt {
+    value: u64,
+    modulus: u64,
+}
+
+impl ModInt {
+    pub fn new(value: u64, modulus: u64) -> Self {
+        assert!(modulus > 0, "the modulus is zero");
+        Self {
+            value: value % modulus,
+            modulus,
+        }
+    }
+
+    /// Returns the representative in `0..modulus`.
+    pub fn value(self) -> u64 {
+        self.value
+    }
+
+    pub fn modulus(self) -> u64 {
+        self.modulus
+    }
+
+    pub fn pow(self, mut exp: u64) -> Self {
+        let (mut base, mut res) = (self, Self::new(1, self.modulus));
+        while exp > 0 {
+            if exp & 1 == 1 {
+                res *= base;
+            }
+            base *= base;
+            exp >>= 1;
+        }
+        res
+    }
+
+    /// Returns the multiplicative inverse if the value and the modulus are coprime.
+    pub fn inverse(self) -> Option<Self> {
+        mod_inverse_wide(self.value as i128, self.modulus as i128).map(|x| Self {
+            value: x as u64,
+            modulus: self.modulus,
+        })
+    }
+
+    fn with_value(self, value: u128) -> Self {
+        Self {
+            value: (value % self.modulus as u128) as u64,
+            modulus: self.modulus,
+        }
+    }
+}
+
+impl Add for ModInt {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        debug_assert_eq!(self.modulus, other.modulus);
+        self.with_value(self.value as u128 + other.value as u128)
+    }
+}
+
+impl Sub for ModInt {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        self + -other
+    }
+}
+
+impl Mul for ModInt {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        debug_assert_eq!(self.modulus, other.modulus);
+        self.with_value(self.value as u128 * other.value as u128)
+    }
+}
+
+impl Neg for ModInt {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        self.with_value((self.modulus - self.value) as u128)
+    }
+}
+
+impl AddAssign for ModInt {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl SubAssign for ModInt {
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
+}
+
+impl MulAssign for ModInt {
+    fn mul_assign(&mut self, other: Self) {
+        *self = *self * other;
+    }
+}
+
+impl std::fmt::Display for ModInt {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gcd_lcm() {
+        assert_eq!(gcd(12u32, 18), 6);
+        assert_eq!(gcd(-12, 18), 6);
+        assert_eq!(gcd(0, 7), 7);
+        assert_eq!(gcd(0u8, 0), 0);
+        assert_eq!(lcm(4, -6), 12);
+        assert_eq!(lcm(0, 5), 0);
+        assert_eq!([23u64, 19, 13, 17].into_iter().fold(1, lcm), 96577);
+    }
+
+    #[test]
+    fn test_inverse() {
+        let (g, x, y) = ext_gcd(240, 46);
+        assert_eq!(g, 2);
+        assert_eq!(240 * x + 46 * y, 2);
+        assert_eq!(mod_inverse(3, 11), Some(4));
+        assert_eq!(mod_inverse(-3, 11), Some(7));
+        assert_eq!(mod_inverse(4, 10), None);
+    }
+
+    #[test]
+    fn test_crt() {
+        assert_eq!(crt([]), Some((0, 1)));
+        assert_eq!(crt([(-1, 4), (5, 6)]), Some((11, 12)));
+        assert_eq!(crt([(1, 4), (2, 6)]), None);
+        // the buses 17,x,13,19 leave one minute apart first at 3417
+        assert_eq!(crt([(0, 17), (-2, 13), (-3, 19)]), Some((3417, 4199)));
+    }
+
+    #[test]
+    fn test_mod_int() {
+        let m = 1_000_000_007;
+        let a = ModInt::new(m - 1, m);
+        let b = ModInt::new(5, m);
+        assert_eq!((a + b).value(), 4);
+        assert_eq!((b - a).value(), 6);
+        assert_eq!((a * a).value(), 1);
+        assert_eq!(-ModInt::new(0, m), ModInt::new(0, m));
+        assert_eq!(b * b.inverse().unwrap(), ModInt::new(1, m));
+        assert_eq!(b.pow(m - 1).value(), 1);
+        assert_eq!(ModInt::new(4, 10).inverse(), None);
+        assert_eq!(ModInt::new(17, 5).to_string(), "2");
+    }
+}
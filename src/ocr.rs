@@ -0,0 +1,337 @@
+//! Recognition of the capital letters the puzzles draw with `#` on a screen, e.g. the CRT of
+//! 2022 day 10. The font is chosen by the height of the screen.
+//!
+//! ```
+//! use advent::ocr::recognize;
+//!
+//! let screen = [
+//!     "#..#.####.#....#.....##.",
+//!     "#..#.#....#....#....#..#",
+//!     "####.###..#....#....#..#",
+//!     "#..#.#....#....#....#..#",
+//!     "#..#.#....#....#....#..#",
+//!     "#..#.####.####.####..##.",
+//! ]
+//! .join("\n");
+//! assert_eq!(recognize(&screen).unwrap(), "HELLO");
+//! ```
+
+/// Fixed-width font, the glyphs are `pitch` columns apart.
+#[derive(Debug, Clone, Copy)]
+pub struct Font {
+    pub width: usize,
+    pub height: usize,
+    pub pitch: usize,
+    glyphs: &'static [(char, &'static [&'static str])],
+}
+
+/// The font of most puzzles, 4×6 pixels with one blank column between the letters.
+pub const SMALL: Font = Font {
+    width: 4,
+    height: 6,
+    pitch: 5,
+    glyphs: &[
+        ('A', &[".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+        ('B', &["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+        ('C', &[".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+        ('E', &["####", "#...", "###.", "#...", "#...", "####"]),
+        ('F', &["####", "#...", "###.", "#...", "#...", "#..."]),
+        ('G', &[".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+        ('H', &["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+        ('I', &[".###", "..#.", "..#.", "..#.", "..#.", ".###"]),
+        ('J', &["..##", "...#", "...#", "...#", "#..#", ".##."]),
+        ('K', &["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+        ('L', &["#...", "#...", "#...", "#...", "#...", "####"]),
+        ('O', &[".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+        ('P', &["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+        ('R', &["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+        ('S', &[".###", "#...", "#...", ".##.", "...#", "###."]),
+        ('U', &["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+        ('Z', &["####", "...#", "..#.", ".#..", "#...", "####"]),
+    ],
+};
+
+/// The font of the message in the sky of 2018 day 10, 6×10 pixels with two blank columns
+/// between the letters.
+pub const LARGE: Font = Font {
+    width: 6,
+    height: 10,
+    pitch: 8,
+    glyphs: &[
+        (
+            'A',
+            &[
+                "..##..", ".#..#.", "#....#", "#....#", "#....#", "######", "#....#", "#....#",
+                "#....#", "#....#",
+            ],
+        ),
+        (
+            'B',
+            &[
+                "#####.", "#....#", "#....#", "#....#", "#####.", "#....#", "#....#", "#....#",
+                "#....#", "#####.",
+            ],
+        ),
+        (
+            'C',
+            &[
+                ".####.", "#....#", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....",
+                "#....#", ".####.",
+            ],
+        ),
+        (
+            'E',
+            &[
+                "######", "#.....", "#.....", "#.....", "#####.", "#.....", "#.....", "#.....",
+                "#.....", "######",
+            ],
+        ),
+        (
+            'F',
+            &[
+                "######", "#.....", "#.....", "#.....", "#####.", "#.....", "#.....", "#.....",
+                "#.....", "#.....",
+            ],
+        ),
+        (
+            'G',
+            &[
+                ".####.", "#....#", "#.....", "#.....", "#.....", "#..###", "#....#", "#....#",
+                "#...##", ".###.#",
+            ],
+        ),
+        (
+            'H',
+            &[
+                "#....#", "#....#", "#....#", "#....#", "######", "#....#", "#....#", "#....#",
+                "#....#", "#....#",
+            ],
+        ),
+        (
+            'J',
+            &[
+                "...###", "....#.", "....#.", "....#.", "....#.", "....#.", "....#.", "#...#.",
+                "#...#.", ".###..",
+            ],
+        ),
+        (
+            'K',
+            &[
+                "#....#", "#...#.", "#..#..", "#.#...", "##....", "##....", "#.#...", "#..#..",
+                "#...#.", "#....#",
+            ],
+        ),
+        (
+            'L',
+            &[
+                "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....",
+                "#.....", "######",
+            ],
+        ),
+        (
+            'N',
+            &[
+                "#....#", "##...#", "##...#", "#.#..#", "#.#..#", "#..#.#", "#..#.#", "#...##",
+                "#...##", "#....#",
+            ],
+        ),
+        (
+            'P',
+            &[
+                "#####.", "#....#", "#....#", "#....#", "#####.", "#.....", "#.....", "#.....",
+                "#.....", "#.....",
+            ],
+        ),
+        (
+            'R',
+            &[
+                "#####.", "#....#", "#....#", "#....#", "#####.", "#..#..", "#...#.", "#...#.",
+                "#....#", "#....#",
+            ],
+        ),
+        (
+            'X',
+            &[
+                "#....#", "#....#", ".#..#.", ".#..#.", "..##..", "..##..", ".#..#.", ".#..#.",
+                "#....#", "#....#",
+            ],
+        ),
+        (
+            'Z',
+            &[
+                "######", ".....#", ".....#", "....#.", "...#..", "..#...", ".#....", "#.....",
+                "#.....", "######",
+            ],
+        ),
+    ],
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OcrError {
+    /// None of the fonts is as high as the screen.
+    Height(usize),
+    /// The glyph at the position does not match any letter. `index` counts the glyphs and
+    /// `column` the pixels from 0, `glyph` is the cell of the screen it was cut from.
+    UnknownGlyph {
+        index: usize,
+        column: usize,
+        glyph: String,
+    },
+}
+
+impl std::fmt::Display for OcrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OcrError::Height(height) => write!(f, "no font is {height} pixels high"),
+            OcrError::UnknownGlyph {
+                index,
+                column,
+                glyph,
+            } => write!(
+                f,
+                "unknown glyph {} at column {}:\n{glyph}",
+                index + 1,
+                column + 1
+            ),
+        }
+    }
+}
+
+impl std::error::Error for OcrError {}
+
+/// Reads the letters of the screen, where `#` is a lit pixel and any other character a dark
+/// one. The blank cells are read as spaces and trimmed at the ends.
+pub fn recognize(screen: &str) -> Result<String, OcrError> {
+    let rows = screen.lines().collect::<Vec<_>>();
+    let font = [SMALL, LARGE]
+        .into_iter()
+        .find(|font| font.height == rows.len())
+        .ok_or(OcrError::Height(rows.len()))?;
+    font.recognize(&rows)
+}
+
+impl Font {
+    /// Reads the letters of the rows of the screen, which is as high as the font.
+    pub fn recognize(&self, rows: &[&str]) -> Result<String, OcrError> {
+        let rows = rows
+            .iter()
+            .map(|row| row.chars().map(|c| c == '#').collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        let width = rows.iter().map(Vec::len).max().unwrap_or_default();
+        let mut res = String::new();
+        for (index, column) in (0..width).step_by(self.pitch).enumerate() {
+            let cell = rows
+                .iter()
+                .map(|row| {
+                    (column..column + self.width)
+                        .map(|col| row.get(col).copied().unwrap_or_default())
+                        .collect::<Vec<_>>()
+                })
+                .collect::<Vec<_>>();
+            let letter = if cell.iter().flatten().all(|lit| !lit) {
+                Some(' ')
+            } else {
+                self.glyphs
+                    .iter()
+                    .find(|(_, glyph)| {
+                        glyph.iter().zip(&cell).all(|(glyph_row, cell_row)| {
+                            glyph_row
+                                .chars()
+                                .map(|c| c == '#')
+                                .eq(cell_row.iter().copied())
+                        })
+                    })
+                    .map(|&(letter, _)| letter)
+            };
+            match letter {
+                Some(letter) => res.push(letter),
+                None => {
+                    let glyph = cell
+                        .iter()
+                        .map(|row| {
+                            row.iter()
+                                .map(|&lit| if lit { '#' } else { '.' })
+                                .collect::<String>()
+                        })
+                        .collect::<Vec<_>>()
+                        .join("\n");
+                    return Err(OcrError::UnknownGlyph {
+                        index,
+                        column,
+                        glyph,
+                    });
+                }
+            }
+        }
+        Ok(res.trim().to_owned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Draws the letters with the font the way the puzzles do.
+    fn render(font: &Font, text: &str) -> String {
+        (0..font.height)
+            .map(|row| {
+                text.chars()
+                    .map(|letter| {
+                        let (_, glyph) = font.glyphs.iter().find(|(c, _)| *c == letter).unwrap();
+                        format!("{:.<pitch$}", glyph[row], pitch = font.pitch)
+                    })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn test_fonts() {
+        for font in [SMALL, LARGE] {
+            let letters = font.glyphs.iter().map(|&(c, _)| c).collect::<String>();
+            assert!(font
+                .glyphs
+                .iter()
+                .all(|(_, glyph)| glyph.len() == font.height
+                    && glyph.iter().all(|row| row.len() == font.width)));
+            assert_eq!(recognize(&render(&font, &letters)).unwrap(), letters);
+        }
+    }
+
+    #[test]
+    fn test_crt() {
+        let screen = "\
+###...##..###..#..#.####.#..#.####...##.
+#..#.#..#.#..#.#.#..#....#.#..#.......#.
+#..#.#..#.#..#.##...###..##...###.....#.
+###..####.###..#.#..#....#.#..#.......#.
+#....#..#.#....#.#..#....#.#..#....#..#.
+#....#..#.#....#..#.#....#..#.####..##..";
+        assert_eq!(recognize(screen).unwrap(), "PAPKFKEJ");
+    }
+
+    #[test]
+    fn test_unknown_glyph() {
+        let screen = "\
+#..#..#...
+#..#..#...
+####..#...
+#..#.#.#..
+#..#.#.#..
+#..#..#...";
+        let error = recognize(screen).unwrap_err();
+        assert_eq!(
+            error,
+            OcrError::UnknownGlyph {
+                index: 1,
+                column: 5,
+                glyph: ".#..\n.#..\n.#..\n#.#.\n#.#.\n.#..".to_owned(),
+            }
+        );
+        assert!(error
+            .to_string()
+            .starts_with("unknown glyph 2 at column 6:\n"));
+        assert_eq!(recognize("#\n#"), Err(OcrError::Height(2)));
+    }
+}
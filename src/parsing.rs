@@ -0,0 +1,249 @@
+use nom::error::{ContextError, ErrorKind, FromExternalError};
+
+pub type IResult<'a, T> = nom::IResult<&'a str, T, Error<'a>>;
+
+/// What the parser was looking for when it failed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Expected {
+    Token(&'static str),
+    Char(char),
+    Context(&'static str),
+    Kind(ErrorKind),
+}
+
+impl std::fmt::Display for Expected {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Expected::Token(token) => write!(f, "{token:?}"),
+            Expected::Char('\n') => write!(f, "new line"),
+            Expected::Char(c) => write!(f, "{c:?}"),
+            Expected::Context(name) => write!(f, "{name}"),
+            Expected::Kind(ErrorKind::Digit) => write!(f, "number"),
+            Expected::Kind(ErrorKind::Alpha) => write!(f, "letter"),
+            Expected::Kind(ErrorKind::Space) => write!(f, "space"),
+            Expected::Kind(ErrorKind::Eof) => write!(f, "end of line"),
+            Expected::Kind(kind) => write!(f, "{}", kind.description()),
+        }
+    }
+}
+
+/// Error of the nom parsers, `input` is the place where parsing failed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Error<'a> {
+    pub input: &'a str,
+    pub expected: Vec<Expected>,
+}
+
+impl<'a> Error<'a> {
+    pub fn new(input: &'a str, expected: Expected) -> Self {
+        Self {
+            input,
+            expected: vec![expected],
+        }
+    }
+}
+
+impl<'a> nom::error::ParseError<&'a str> for Error<'a> {
+    fn from_error_kind(input: &'a str, kind: ErrorKind) -> Self {
+        Error::new(input, Expected::Kind(kind))
+    }
+
+    fn append(_: &'a str, _: ErrorKind, other: Self) -> Self {
+        other
+    }
+
+    fn from_char(input: &'a str, c: char) -> Self {
+        Error::new(input, Expected::Char(c))
+    }
+
+    fn or(mut self, other: Self) -> Self {
+        // the alternative that went further is the most relevant one
+        match self.input.len().cmp(&other.input.len()) {
+            std::cmp::Ordering::Less => self,
+            std::cmp::Ordering::Greater => other,
+            std::cmp::Ordering::Equal => {
+                for expected in other.expected {
+                    if !self.expected.contains(&expected) {
+                        self.expected.push(expected);
+                    }
+                }
+                self
+            }
+        }
+    }
+}
+
+impl<'a> ContextError<&'a str> for Error<'a> {
+    fn add_context(input: &'a str, context: &'static str, other: Self) -> Self {
+        if input.len() == other.input.len() {
+            Error::new(input, Expected::Context(context))
+        } else {
+            other
+        }
+    }
+}
+
+impl<'a, E> FromExternalError<&'a str, E> for Error<'a> {
+    fn from_external_error(input: &'a str, kind: ErrorKind, _: E) -> Self {
+        Error::new(input, Expected::Kind(kind))
+    }
+}
+
+/// Same as [`nom::bytes::complete::tag`], but remembers the token when it is not found.
+pub fn tag<'a>(token: &'static str) -> impl FnMut(&'a str) -> IResult<'a, &'a str> {
+    move |input: &'a str| {
+        nom::bytes::complete::tag::<_, _, Error<'a>>(token)(input)
+            .map_err(|e| e.map(|e| Error::new(e.input, Expected::Token(token))))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub puzzle: Option<(u16, u8)>,
+    pub line: usize,
+    pub column: usize,
+    pub expected: Vec<Expected>,
+    pub found: Option<String>,
+}
+
+impl ParseError {
+    /// Creates the error out of nom error, `source` is the whole input
+    /// the failed input was sliced from.
+    pub fn new(source: &str, error: Error) -> Self {
+        let offset = (error.input.as_ptr() as usize)
+            .checked_sub(source.as_ptr() as usize)
+            .filter(|offset| *offset <= source.len())
+            .unwrap_or_default();
+        let before = &source[..offset];
+        let line = before.matches('\n').count() + 1;
+        let column = before
+            .rsplit('\n')
+            .next()
+            .map(|s| s.chars().count())
+            .unwrap_or_default()
+            + 1;
+        let found = error
+            .input
+            .lines()
+            .next()
+            .filter(|line| !line.is_empty())
+            .map(|line| line.chars().take(20).collect());
+        Self {
+            puzzle: None,
+            line,
+            column,
+            expected: error.expected,
+            found,
+        }
+    }
+
+    pub fn in_puzzle(mut self, year: u16, day: u8) -> Self {
+        self.puzzle = Some((year, day));
+        self
+    }
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some((year, day)) = self.puzzle {
+            write!(f, "{year} day {day}, ")?;
+        }
+        write!(f, "line {}, column {}: expected ", self.line, self.column)?;
+        for (ind, expected) in self.expected.iter().enumerate() {
+            if ind > 0 {
+                write!(f, " or ")?;
+            }
+            write!(f, "{expected}")?;
+        }
+        match &self.found {
+            Some(found) => write!(f, ", found {found:?}"),
+            None => write!(f, ", found end of line"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Runs `parser` over the whole `input`, which is a part of `source`,
+/// the position of the error is reported relative to the `source`.
+pub fn parse_all<'a, T>(
+    source: &'a str,
+    input: &'a str,
+    parser: impl FnMut(&'a str) -> IResult<'a, T>,
+) -> Result<T, ParseError> {
+    match nom::combinator::all_consuming(parser)(input) {
+        Ok((_, value)) => Ok(value),
+        Err(nom::Err::Error(e) | nom::Err::Failure(e)) => Err(ParseError::new(source, e)),
+        Err(nom::Err::Incomplete(_)) => unreachable!("complete parsers are used"),
+    }
+}
+
+/// Parses every line of `file_content` with `parser`.
+pub fn parse_lines<'a, T: 'a>(
+    file_content: &'a str,
+    mut parser: impl FnMut(&'a str) -> IResult<'a, T> + 'a,
+) -> impl Iterator<Item = Result<T, ParseError>> + 'a {
+    file_content
+        .lines()
+        .map(move |line| parse_all(file_content, line, &mut parser))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nom::{branch::alt, character::complete, sequence::separated_pair};
+
+    fn parse_point(input: &str) -> IResult<'_, (i32, i32)> {
+        separated_pair(complete::i32, tag(", "), complete::i32)(input)
+    }
+
+    #[test]
+    fn test_position() {
+        let source = "1, 2\n3, 4\n5; 6";
+        let errors = parse_lines(source, parse_point)
+            .filter_map(Result::err)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            errors,
+            vec![ParseError {
+                puzzle: None,
+                line: 3,
+                column: 2,
+                expected: vec![Expected::Token(", ")],
+                found: Some("; 6".to_owned()),
+            }]
+        );
+        assert_eq!(
+            errors[0].to_string(),
+            "line 3, column 2: expected \", \", found \"; 6\""
+        );
+    }
+
+    #[test]
+    fn test_trailing_input() {
+        let error = parse_all("1, 2x", "1, 2x", parse_point).unwrap_err();
+        assert_eq!(
+            error.in_puzzle(2022, 15).to_string(),
+            "2022 day 15, line 1, column 5: expected end of line, found \"x\""
+        );
+    }
+
+    #[test]
+    fn test_alternatives() {
+        let mut parser = alt((tag("noop"), tag("addx")));
+        let error = parse_all("addy", "addy", &mut parser).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 1, column 1: expected \"noop\" or \"addx\", found \"addy\""
+        );
+    }
+
+    #[test]
+    fn test_end_of_line() {
+        let error = parse_all("1, ", "1, ", parse_point).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 1, column 4: expected number, found end of line"
+        );
+    }
+}
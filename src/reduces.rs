@@ -1,61 +1,199 @@
-use std::mem;
+//! Iterator adapters that fold consecutive items into groups, the reducer decides where
+//! a group ends. The item that ends a group is given to the reducer too, so it can be kept
+//! or dropped like a separator. The group that is not ended when the items run out is
+//! yielded last, unless it is empty, so a trailing separator does not add a group.
+//!
+//! ```
+//! use advent::reduces::Reduces;
+//!
+//! let sums = "1\n2\n\n3\n".lines().reduces(0, |sum, line| match line.parse::<u32>() {
+//!     Ok(value) => {
+//!         *sum += value;
+//!         true
+//!     }
+//!     Err(_) => false,
+//! });
+//! assert_eq!(sums.collect::<Vec<_>>(), vec![3, 3]);
+//! ```
+use std::iter::FusedIterator;
 
-pub struct ReducesIter<Iter, Value, Reducer>
+#[derive(Clone)]
+pub struct ReducesIter<Iter, Value, Init, Reducer> {
+    iter: Iter,
+    init: Init,
+    current_value: Option<Value>,
+    reducer: Reducer,
+}
+
+impl<Iter, Value, Init, Reducer> Iterator for ReducesIter<Iter, Value, Init, Reducer>
 where
     Iter: Iterator,
-    Reducer: Fn(&mut Value, Iter::Item) -> bool,
+    Init: FnMut() -> Value,
+    Reducer: FnMut(&mut Value, Iter::Item) -> bool,
+{
+    type Item = Value;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for entry in self.iter.by_ref() {
+            let current_value = self.current_value.get_or_insert_with(&mut self.init);
+            if !(self.reducer)(current_value, entry) {
+                return self.current_value.take();
+            }
+        }
+        self.current_value.take()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        group_hint(self.iter.size_hint(), self.current_value.is_some())
+    }
+}
+
+impl<Iter, Value, Init, Reducer> FusedIterator for ReducesIter<Iter, Value, Init, Reducer>
+where
+    Iter: FusedIterator,
+    Init: FnMut() -> Value,
+    Reducer: FnMut(&mut Value, Iter::Item) -> bool,
 {
+}
+
+/// Same as [`ReducesIter`], but the reducer can fail. The error is yielded in place of
+/// the group and ends the iteration.
+#[derive(Clone)]
+pub struct TryReducesIter<Iter, Value, Init, Reducer> {
     iter: Iter,
-    initial: Value,
+    init: Init,
     current_value: Option<Value>,
     reducer: Reducer,
+    failed: bool,
 }
 
-impl<Iter, Value, Reducer> Iterator for ReducesIter<Iter, Value, Reducer>
+impl<Iter, Value, Init, Reducer, Error> Iterator for TryReducesIter<Iter, Value, Init, Reducer>
 where
     Iter: Iterator,
-    Reducer: Fn(&mut Value, Iter::Item) -> bool,
-    Value: Clone,
+    Init: FnMut() -> Value,
+    Reducer: FnMut(&mut Value, Iter::Item) -> Result<bool, Error>,
 {
-    type Item = Value;
+    type Item = Result<Value, Error>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        loop {
-            let entry = self.iter.next();
-            if entry.is_none() {
-                if self.current_value.is_none() {
-                    return None;
+        if self.failed {
+            return None;
+        }
+        for entry in self.iter.by_ref() {
+            let current_value = self.current_value.get_or_insert_with(&mut self.init);
+            match (self.reducer)(current_value, entry) {
+                Ok(true) => {}
+                Ok(false) => return self.current_value.take().map(Ok),
+                Err(error) => {
+                    self.failed = true;
+                    self.current_value = None;
+                    return Some(Err(error));
                 }
-                return mem::take(&mut self.current_value);
             }
-            let mut current_value = self
-                .current_value
-                .take()
-                .unwrap_or_else(|| self.initial.clone());
-            let entry_value = entry.unwrap();
-            let should_continue = (self.reducer)(&mut current_value, entry_value);
-
-            if !should_continue {
-                return Some(current_value);
-            } else {
-                self.current_value = Some(current_value);
+        }
+        self.current_value.take().map(Ok)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.failed {
+            return (0, Some(0));
+        }
+        group_hint(self.iter.size_hint(), self.current_value.is_some())
+    }
+}
+
+impl<Iter, Value, Init, Reducer, Error> FusedIterator for TryReducesIter<Iter, Value, Init, Reducer>
+where
+    Iter: FusedIterator,
+    Init: FnMut() -> Value,
+    Reducer: FnMut(&mut Value, Iter::Item) -> Result<bool, Error>,
+{
+}
+
+/// Groups the runs of items that match the predicate, see [`Reduces::group_while`].
+#[derive(Clone)]
+pub struct GroupWhile<Iter, Predicate> {
+    iter: Iter,
+    predicate: Predicate,
+}
+
+impl<Iter, Predicate> Iterator for GroupWhile<Iter, Predicate>
+where
+    Iter: Iterator,
+    Predicate: FnMut(&Iter::Item) -> bool,
+{
+    type Item = Vec<Iter::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut group = None;
+        for item in self.iter.by_ref() {
+            if !(self.predicate)(&item) {
+                return Some(group.unwrap_or_default());
             }
+            group.get_or_insert_with(Vec::new).push(item);
         }
+        group
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        group_hint(self.iter.size_hint(), false)
     }
 }
 
+impl<Iter, Predicate> FusedIterator for GroupWhile<Iter, Predicate>
+where
+    Iter: FusedIterator,
+    Predicate: FnMut(&Iter::Item) -> bool,
+{
+}
+
+/// Every item ends at most one group and the started group is yielded at the end.
+fn group_hint((lower, upper): (usize, Option<usize>), started: bool) -> (usize, Option<usize>) {
+    let lower = usize::from(lower > 0 || started);
+    let upper = upper.and_then(|upper| upper.checked_add(usize::from(started)));
+    (lower, upper)
+}
+
 pub trait Reduces<Iter>
 where
     Iter: Iterator,
 {
+    /// Folds the items into groups starting from a clone of `initial`, the group ends when
+    /// `reducer` returns `false`.
     fn reduces<Value, Reducer>(
         self,
         initial: Value,
         reducer: Reducer,
-    ) -> ReducesIter<Iter, Value, Reducer>
+    ) -> ReducesIter<Iter, Value, impl FnMut() -> Value + Clone, Reducer>
     where
-        Reducer: Fn(&mut Value, Iter::Item) -> bool,
+        Reducer: FnMut(&mut Value, Iter::Item) -> bool,
         Value: Clone;
+
+    /// Same as [`Reduces::reduces`], but every group starts from the value made by `init`.
+    fn reduces_with<Value, Init, Reducer>(
+        self,
+        init: Init,
+        reducer: Reducer,
+    ) -> ReducesIter<Iter, Value, Init, Reducer>
+    where
+        Init: FnMut() -> Value,
+        Reducer: FnMut(&mut Value, Iter::Item) -> bool;
+
+    /// Same as [`Reduces::reduces_with`], but stops at the first error of `reducer`.
+    fn try_reduces<Value, Init, Reducer, Error>(
+        self,
+        init: Init,
+        reducer: Reducer,
+    ) -> TryReducesIter<Iter, Value, Init, Reducer>
+    where
+        Init: FnMut() -> Value,
+        Reducer: FnMut(&mut Value, Iter::Item) -> Result<bool, Error>;
+
+    /// Collects the runs of items that match `predicate`, the items that do not are dropped
+    /// as separators.
+    fn group_while<Predicate>(self, predicate: Predicate) -> GroupWhile<Iter, Predicate>
+    where
+        Predicate: FnMut(&Iter::Item) -> bool;
 }
 
 impl<Iter> Reduces<Iter> for Iter
@@ -66,16 +204,214 @@ where
         self,
         initial: Value,
         reducer: Reducer,
-    ) -> ReducesIter<Iter, Value, Reducer>
+    ) -> ReducesIter<Iter, Value, impl FnMut() -> Value + Clone, Reducer>
     where
-        Reducer: Fn(&mut Value, Iter::Item) -> bool,
+        Reducer: FnMut(&mut Value, Iter::Item) -> bool,
         Value: Clone,
+    {
+        self.reduces_with(move || initial.clone(), reducer)
+    }
+
+    fn reduces_with<Value, Init, Reducer>(
+        self,
+        init: Init,
+        reducer: Reducer,
+    ) -> ReducesIter<Iter, Value, Init, Reducer>
+    where
+        Init: FnMut() -> Value,
+        Reducer: FnMut(&mut Value, Iter::Item) -> bool,
     {
         ReducesIter {
             iter: self,
-            initial,
+            init,
+            current_value: None,
+            reducer,
+        }
+    }
+
+    fn try_reduces<Value, Init, Reducer, Error>(
+        self,
+        init: Init,
+        reducer: Reducer,
+    ) -> TryReducesIter<Iter, Value, Init, Reducer>
+    where
+        Init: FnMut() -> Value,
+        Reducer: FnMut(&mut Value, Iter::Item) -> Result<bool, Error>,
+    {
+        TryReducesIter {
+            iter: self,
+            init,
             current_value: None,
             reducer,
+            failed: false,
+        }
+    }
+
+    fn group_while<Predicate>(self, predicate: Predicate) -> GroupWhile<Iter, Predicate>
+    where
+        Predicate: FnMut(&Iter::Item) -> bool,
+    {
+        GroupWhile {
+            iter: self,
+            predicate,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SEPARATOR: u8 = 0;
+
+    /// Every sequence of the length up to `max_len` over the alphabet `0..=2`, 0 being
+    /// the separator.
+    fn sequences(max_len: u32) -> impl Iterator<Item = Vec<u8>> {
+        (0..=max_len).flat_map(|len| {
+            (0..3usize.pow(len)).map(move |mut code| {
+                (0..len)
+                    .map(|_| {
+                        let item = (code % 3) as u8;
+                        code /= 3;
+                        item
+                    })
+                    .collect()
+            })
+        })
+    }
+
+    /// The groups split by the separators, like `str::lines` there is no group after
+    /// the trailing separator.
+    fn model(items: &[u8]) -> Vec<Vec<u8>> {
+        let mut groups = items
+            .split(|&item| item == SEPARATOR)
+            .map(<[u8]>::to_vec)
+            .collect::<Vec<_>>();
+        if groups.last().is_some_and(Vec::is_empty) {
+            groups.pop();
+        }
+        groups
+    }
+
+    /// Checks that the size hint holds the number of the remaining groups at every step.
+    fn assert_hints<I: Iterator + Clone>(mut iter: I) {
+        loop {
+            let (lower, upper) = iter.size_hint();
+            let remaining = iter.clone().count();
+            assert!(lower <= remaining && upper.is_none_or(|upper| remaining <= upper));
+            if iter.next().is_none() {
+                break;
+            }
+        }
+    }
+
+    #[test]
+    fn test_reduces() {
+        for items in sequences(7) {
+            let groups = items
+                .iter()
+                .reduces(Vec::new(), |group, &item| {
+                    if item == SEPARATOR {
+                        return false;
+                    }
+                    group.push(item);
+                    true
+                })
+                .collect::<Vec<_>>();
+            assert_eq!(groups, model(&items), "{items:?}");
+        }
+    }
+
+    #[test]
+    fn test_reduces_with() {
+        struct Group(u32);
+
+        for items in sequences(7) {
+            let sums = items
+                .iter()
+                .reduces_with(
+                    || Group(0),
+                    |group, &item| {
+                        group.0 += u32::from(item);
+                        item != SEPARATOR
+                    },
+                )
+                .map(|group| group.0)
+                .collect::<Vec<_>>();
+            let expected = model(&items)
+                .iter()
+                .map(|group| group.iter().map(|&item| u32::from(item)).sum())
+                .collect::<Vec<u32>>();
+            assert_eq!(sums, expected, "{items:?}");
+        }
+    }
+
+    #[test]
+    fn test_group_while() {
+        for items in sequences(7) {
+            let groups = items
+                .iter()
+                .copied()
+                .group_while(|&item| item != SEPARATOR)
+                .collect::<Vec<_>>();
+            assert_eq!(groups, model(&items), "{items:?}");
+        }
+        let lines = "a\nb\n\nc\n".lines().group_while(|line| !line.is_empty());
+        assert_eq!(lines.collect::<Vec<_>>(), vec![vec!["a", "b"], vec!["c"]]);
+    }
+
+    #[test]
+    fn test_try_reduces() {
+        for items in sequences(7) {
+            // 2 fails, so the groups before it are yielded and then the error
+            let results = items
+                .iter()
+                .try_reduces(Vec::new, |group, &item| match item {
+                    SEPARATOR => Ok(false),
+                    2 => Err(group.len()),
+                    _ => {
+                        group.push(item);
+                        Ok(true)
+                    }
+                })
+                .collect::<Vec<_>>();
+            let expected = match items.iter().position(|&item| item == 2) {
+                Some(ind) => {
+                    let mut res = model(&items[..ind]).into_iter().map(Ok).collect::<Vec<_>>();
+                    let started = items[..ind]
+                        .iter()
+                        .rev()
+                        .take_while(|&&item| item != SEPARATOR)
+                        .count();
+                    if started > 0 {
+                        res.pop();
+                    }
+                    res.push(Err(started));
+                    res
+                }
+                None => model(&items).into_iter().map(Ok).collect(),
+            };
+            assert_eq!(results, expected, "{items:?}");
+        }
+    }
+
+    #[test]
+    fn test_size_hint() {
+        for items in sequences(6) {
+            assert_hints(items.iter().reduces((), |_, &item| item != SEPARATOR));
+            assert_hints(items.iter().group_while(|&&item| item != SEPARATOR));
+            assert_hints(items.iter().try_reduces(
+                || (),
+                |_, &item| match item {
+                    2 => Err(()),
+                    _ => Ok(item != SEPARATOR),
+                },
+            ));
         }
+        let mut iter = [1, 0, 1].iter().group_while(|&&item| item != SEPARATOR);
+        assert_eq!(iter.size_hint(), (1, Some(3)));
+        assert_eq!(iter.by_ref().count(), 2);
+        assert_eq!(iter.size_hint(), (0, Some(0)));
+        assert_eq!(iter.next(), None);
     }
 }
@@ -0,0 +1,81 @@
+use crate::solution::Solution;
+
+#[derive(Debug, Default)]
+pub struct Registry {
+    solutions: Vec<&'static dyn Solution>,
+}
+
+impl Registry {
+    pub fn register(&mut self, solution: &'static dyn Solution) {
+        debug_assert!(
+            self.get(solution.year(), solution.day()).is_none(),
+            "{solution:?} is registered twice"
+        );
+        self.solutions.push(solution);
+        self.solutions.sort_by_key(|s| (s.year(), s.day()));
+    }
+
+    pub fn get(&self, year: u16, day: u8) -> Option<&'static dyn Solution> {
+        self.iter().find(|s| s.year() == year && s.day() == day)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &'static dyn Solution> + '_ {
+        self.solutions.iter().copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_every_module_is_registered() {
+        let registry = crate::registry();
+        let puzzles = include_str!("lib.rs")
+            .lines()
+            .filter_map(|line| line.strip_prefix("pub mod y")?.strip_suffix(';'))
+            .map(|module| {
+                let (year, day) = module.split_once('d').unwrap();
+                (
+                    2000 + year.parse::<u16>().unwrap(),
+                    day.parse::<u8>().unwrap(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert!(!puzzles.is_empty());
+        for (year, day) in puzzles {
+            assert!(
+                registry.get(year, day).is_some(),
+                "{year} day {day} is not registered"
+            );
+        }
+        assert!(registry.get(2022, 1).is_some());
+    }
+
+    #[test]
+    fn test_solutions_are_sorted() {
+        let registry = crate::registry();
+        let ids = registry
+            .iter()
+            .map(|s| (s.year(), s.day()))
+            .collect::<Vec<_>>();
+        let mut sorted = ids.clone();
+        sorted.sort();
+        assert_eq!(ids, sorted);
+    }
+
+    #[test]
+    fn test_solve() {
+        let registry = crate::registry();
+        let solution = registry.get(2022, 6).unwrap();
+        let params = solution.params();
+        assert!(solution.solve(0, "abcd", &params).is_none());
+        assert_eq!(
+            solution
+                .solve(1, "abcd", &params)
+                .unwrap()
+                .unwrap()
+                .to_string(),
+            "4"
+        );
+        assert!(solution.solve(3, "abcd", &params).is_none());
+    }
+}
@@ -0,0 +1,184 @@
+//! Pictures of the puzzles saved as Netpbm images, which need no encoder: PBM for black and
+//! white pictures and PPM for colored ones. Every pixel of an [`Image`] is an index into its
+//! palette, so the colors can be changed without rendering the puzzle again.
+//!
+//! ```
+//! use advent::render::Image;
+//!
+//! let image = Image::from_text("#.\n.#", |c| u8::from(c == '#'));
+//! let mut pbm = Vec::new();
+//! image.write_pbm(&mut pbm, 2).unwrap();
+//! assert_eq!(pbm, b"P4\n4 4\n\xc0\xc0\x30\x30");
+//! ```
+use std::io::{self, Write};
+
+use crate::grid::Grid;
+
+pub type Rgb = [u8; 3];
+
+pub const WHITE: Rgb = [0xff, 0xff, 0xff];
+pub const BLACK: Rgb = [0, 0, 0];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    pixels: Grid<u8>,
+    palette: Vec<Rgb>,
+}
+
+impl Image {
+    /// Creates the image out of the palette indices, the palette is black on white.
+    pub fn new(pixels: Grid<u8>) -> Self {
+        Self {
+            pixels,
+            palette: vec![WHITE, BLACK],
+        }
+    }
+
+    pub fn from_grid<T>(grid: &Grid<T>, pixel: impl FnMut(&T) -> u8) -> Self {
+        Self::new(grid.map(pixel))
+    }
+
+    /// Creates the image out of a picture drawn with characters, the shorter lines are
+    /// padded with 0.
+    pub fn from_text(text: &str, mut pixel: impl FnMut(char) -> u8) -> Self {
+        let rows = text.lines().count();
+        let cols = text
+            .lines()
+            .map(|line| line.chars().count())
+            .max()
+            .unwrap_or_default();
+        let mut pixels = Grid::new(rows, cols, 0);
+        for (row, line) in text.lines().enumerate() {
+            for (col, c) in line.chars().enumerate() {
+                pixels[(row, col)] = pixel(c);
+            }
+        }
+        Self::new(pixels)
+    }
+
+    pub fn with_palette(mut self, palette: Vec<Rgb>) -> Self {
+        self.palette = palette;
+        self
+    }
+
+    pub fn width(&self) -> usize {
+        self.pixels.cols()
+    }
+
+    pub fn height(&self) -> usize {
+        self.pixels.rows()
+    }
+
+    /// Returns the palette indices of the pixels.
+    pub fn pixels(&self) -> &Grid<u8> {
+        &self.pixels
+    }
+
+    pub fn pixels_mut(&mut self) -> &mut Grid<u8> {
+        &mut self.pixels
+    }
+
+    pub fn palette(&self) -> &[Rgb] {
+        &self.palette
+    }
+
+    /// Returns the color of the palette index, the indices past the palette are black.
+    pub fn color(&self, index: u8) -> Rgb {
+        self.palette.get(index as usize).copied().unwrap_or(BLACK)
+    }
+
+    /// Writes the binary PBM image, where the pixels of the index 0 are white and the other
+    /// ones are black. Every pixel becomes a square of `scale` pixels.
+    pub fn write_pbm(&self, out: &mut impl Write, scale: usize) -> io::Result<()> {
+        let (width, height) = (self.width() * scale, self.height() * scale);
+        write!(out, "P4\n{width} {height}\n")?;
+        let mut line = vec![0u8; width.div_ceil(8)];
+        for row in 0..self.height() {
+            line.fill(0);
+            for (col, &index) in self.pixels.row(row).iter().enumerate() {
+                if index == 0 {
+                    continue;
+                }
+                for x in col * scale..(col + 1) * scale {
+                    line[x / 8] |= 0x80 >> (x % 8);
+                }
+            }
+            for _ in 0..scale {
+                out.write_all(&line)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes the binary PPM image in the colors of the palette. Every pixel becomes a square
+    /// of `scale` pixels.
+    pub fn write_ppm(&self, out: &mut impl Write, scale: usize) -> io::Result<()> {
+        let (width, height) = (self.width() * scale, self.height() * scale);
+        write!(out, "P6\n{width} {height}\n255\n")?;
+        let mut line = Vec::with_capacity(width * 3);
+        for row in 0..self.height() {
+            line.clear();
+            for &index in self.pixels.row(row) {
+                let color = self.color(index);
+                for _ in 0..scale {
+                    line.extend_from_slice(&color);
+                }
+            }
+            for _ in 0..scale {
+                out.write_all(&line)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Returns `steps` colors going evenly from one color to the other.
+pub fn gradient(from: Rgb, to: Rgb, steps: usize) -> Vec<Rgb> {
+    (0..steps)
+        .map(|step| {
+            let mut color = from;
+            for (channel, (&from, &to)) in color.iter_mut().zip(from.iter().zip(&to)) {
+                let delta = (to as i32 - from as i32) * step as i32 / (steps - 1).max(1) as i32;
+                *channel = (from as i32 + delta) as u8;
+            }
+            color
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pbm() {
+        let image = Image::from_text("#########\n.\n", |c| u8::from(c == '#'));
+        assert_eq!((image.width(), image.height()), (9, 2));
+        let mut pbm = Vec::new();
+        image.write_pbm(&mut pbm, 1).unwrap();
+        assert_eq!(pbm, b"P4\n9 2\n\xff\x80\x00\x00");
+    }
+
+    #[test]
+    fn test_ppm() {
+        let image =
+            Image::from_text("ab", |c| c as u8 - b'a' + 1).with_palette(vec![BLACK, [1, 2, 3]]);
+        assert_eq!(image.color(2), BLACK);
+        let mut ppm = Vec::new();
+        image.write_ppm(&mut ppm, 2).unwrap();
+        let pixels = [[1, 2, 3], [1, 2, 3], BLACK, BLACK].concat();
+        assert_eq!(
+            ppm,
+            [b"P6\n4 2\n255\n".as_slice(), &pixels, &pixels].concat()
+        );
+    }
+
+    #[test]
+    fn test_gradient() {
+        assert_eq!(
+            gradient([0, 100, 255], [255, 0, 255], 3),
+            vec![[0, 100, 255], [127, 50, 255], [255, 0, 255]]
+        );
+        assert_eq!(gradient(BLACK, WHITE, 1), vec![BLACK]);
+    }
+}
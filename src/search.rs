@@ -0,0 +1,607 @@
+//! Searches over graphs given by a successor function. [`bfs`] and [`dijkstra`] are
+//! iterators over the reached nodes in the order of their distance, so the search stops as
+//! soon as the caller stops asking, and [`astar`] looks for the shortest path to a goal.
+//!
+//! The visited nodes are kept in a [`Tree`] with the distances to them and, when enabled with
+//! `with_paths`, the predecessors the shortest paths are reconstructed from.
+//!
+//! ```
+//! use advent::search::bfs;
+//!
+//! // numbers reachable from 1 by doubling or adding 3
+//! let mut search = bfs([1], |n: &u32| [n * 2, n + 3]).with_paths();
+//! let (_, steps) = search.find(|(n, _)| *n == 11).unwrap();
+//! assert_eq!(steps, 3);
+//! assert_eq!(search.tree().path(&11), Some(vec![1, 4, 8, 11]));
+//! ```
+use std::{
+    cmp::Ordering,
+    collections::{hash_map::Entry, BinaryHeap, HashMap, VecDeque},
+    hash::Hash,
+    marker::PhantomData,
+    ops::Add,
+};
+
+use crate::grid::{Grid, Pos};
+
+/// Graph given by the nodes reachable from a node in one step, closures returning an
+/// iterable are graphs too.
+pub trait Successors<N> {
+    type Iter: IntoIterator<Item = N>;
+
+    fn successors(&self, node: &N) -> Self::Iter;
+}
+
+impl<N, I, F> Successors<N> for F
+where
+    F: Fn(&N) -> I,
+    I: IntoIterator<Item = N>,
+{
+    type Iter = I;
+
+    fn successors(&self, node: &N) -> I {
+        self(node)
+    }
+}
+
+/// Graph whose edges have costs, the successors are returned with the cost of the step.
+pub trait WeightedSuccessors<N, C> {
+    type Iter: IntoIterator<Item = (N, C)>;
+
+    fn successors(&self, node: &N) -> Self::Iter;
+}
+
+impl<N, C, I, F> WeightedSuccessors<N, C> for F
+where
+    F: Fn(&N) -> I,
+    I: IntoIterator<Item = (N, C)>,
+{
+    type Iter = I;
+
+    fn successors(&self, node: &N) -> I {
+        self(node)
+    }
+}
+
+/// Cost of a path, `Default` is the cost of the empty one.
+pub trait Cost: Copy + Ord + Default + Add<Output = Self> {}
+
+impl<C: Copy + Ord + Default + Add<Output = C>> Cost for C {}
+
+/// How a node was reached, kept by the search for every reached node.
+#[derive(Debug, Clone)]
+pub struct Visit<N, C> {
+    cost: C,
+    parent: Option<N>,
+    settled: bool,
+}
+
+/// Storage of the visits of the reached nodes. [`HashMap`] works for any nodes, a [`Grid`]
+/// of options is faster for the positions of a map.
+pub trait Visits<N, C> {
+    fn get(&self, node: &N) -> Option<&Visit<N, C>>;
+    fn get_mut(&mut self, node: &N) -> Option<&mut Visit<N, C>>;
+    /// Returns the visit of the node, inserting a new one if there is none. The flag tells
+    /// whether the visit is new.
+    fn get_or_insert_with(
+        &mut self,
+        node: N,
+        visit: impl FnOnce() -> Visit<N, C>,
+    ) -> (&mut Visit<N, C>, bool);
+    fn iter<'a>(&'a self) -> impl Iterator<Item = (N, &'a Visit<N, C>)>
+    where
+        N: 'a,
+        C: 'a;
+}
+
+impl<N: Clone + Eq + Hash, C> Visits<N, C> for HashMap<N, Visit<N, C>> {
+    fn get(&self, node: &N) -> Option<&Visit<N, C>> {
+        HashMap::get(self, node)
+    }
+
+    fn get_mut(&mut self, node: &N) -> Option<&mut Visit<N, C>> {
+        HashMap::get_mut(self, node)
+    }
+
+    #[inline]
+    fn get_or_insert_with(
+        &mut self,
+        node: N,
+        visit: impl FnOnce() -> Visit<N, C>,
+    ) -> (&mut Visit<N, C>, bool) {
+        match self.entry(node) {
+            Entry::Occupied(entry) => (entry.into_mut(), false),
+            Entry::Vacant(entry) => (entry.insert(visit()), true),
+        }
+    }
+
+    fn iter<'a>(&'a self) -> impl Iterator<Item = (N, &'a Visit<N, C>)>
+    where
+        N: 'a,
+        C: 'a,
+    {
+        HashMap::iter(self).map(|(node, visit)| (node.clone(), visit))
+    }
+}
+
+impl<C> Visits<Pos, C> for Grid<Option<Visit<Pos, C>>> {
+    fn get(&self, pos: &Pos) -> Option<&Visit<Pos, C>> {
+        Grid::get(self, *pos)?.as_ref()
+    }
+
+    fn get_mut(&mut self, pos: &Pos) -> Option<&mut Visit<Pos, C>> {
+        Grid::get_mut(self, *pos)?.as_mut()
+    }
+
+    #[inline]
+    fn get_or_insert_with(
+        &mut self,
+        pos: Pos,
+        visit: impl FnOnce() -> Visit<Pos, C>,
+    ) -> (&mut Visit<Pos, C>, bool) {
+        let cell = &mut self[pos];
+        let inserted = cell.is_none();
+        (cell.get_or_insert_with(visit), inserted)
+    }
+
+    fn iter<'a>(&'a self) -> impl Iterator<Item = (Pos, &'a Visit<Pos, C>)>
+    where
+        C: 'a,
+    {
+        Grid::iter(self).filter_map(|(pos, visit)| Some((pos, visit.as_ref()?)))
+    }
+}
+
+/// Nodes reached by a search with the costs of the shortest paths to them.
+#[derive(Debug, Clone)]
+pub struct Tree<N, C, M = HashMap<N, Visit<N, C>>> {
+    visits: M,
+    len: usize,
+    paths: bool,
+    marker: PhantomData<(N, C)>,
+}
+
+impl<N: Clone, C: Cost, M: Visits<N, C>> Tree<N, C, M> {
+    fn new(visits: M) -> Self {
+        Self {
+            visits,
+            len: 0,
+            paths: false,
+            marker: PhantomData,
+        }
+    }
+
+    /// Records the start node, returns `false` if it is already known.
+    fn start(&mut self, node: N) -> bool {
+        self.reach(node, C::default(), None)
+    }
+
+    /// Records the path to the node unless a path that is not longer is already known.
+    #[inline]
+    fn reach(&mut self, node: N, cost: C, parent: Option<&N>) -> bool {
+        let parent = parent.filter(|_| self.paths);
+        let new_visit = || Visit {
+            cost,
+            parent: parent.cloned(),
+            settled: false,
+        };
+        match self.visits.get_or_insert_with(node, new_visit) {
+            (_, true) => {
+                self.len += 1;
+                true
+            }
+            (visit, false) if !visit.settled && cost < visit.cost => {
+                visit.cost = cost;
+                visit.parent = parent.cloned();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Records the first path to the node, the later ones are never shorter when every
+    /// step costs the same.
+    #[inline]
+    fn reach_first(&mut self, node: N, cost: C, parent: &N) -> bool {
+        let paths = self.paths;
+        let new_visit = || Visit {
+            cost,
+            parent: paths.then(|| parent.clone()),
+            settled: true,
+        };
+        let (_, inserted) = self.visits.get_or_insert_with(node, new_visit);
+        self.len += usize::from(inserted);
+        inserted
+    }
+
+    /// Marks the node as final if it is reached with the cost, returns `false` for outdated
+    /// entries of the queue.
+    fn settle(&mut self, node: &N, cost: C) -> bool {
+        match self.visits.get_mut(node) {
+            Some(visit) if !visit.settled && visit.cost == cost => {
+                visit.settled = true;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn contains(&self, node: &N) -> bool {
+        self.visits.get(node).is_some()
+    }
+
+    /// Returns the cost of the best path to the node found so far.
+    pub fn cost(&self, node: &N) -> Option<C> {
+        self.visits.get(node).map(|visit| visit.cost)
+    }
+
+    pub fn costs(&self) -> impl Iterator<Item = (N, C)> + '_ {
+        self.visits.iter().map(|(node, visit)| (node, visit.cost))
+    }
+
+    /// Returns the nodes of the best path found from a start node to the node, both ends
+    /// included.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the search was not asked to record the paths.
+    pub fn path(&self, node: &N) -> Option<Vec<N>> {
+        assert!(self.paths, "the search does not record the paths");
+        let mut visit = self.visits.get(node)?;
+        let mut path = vec![node.clone()];
+        while let Some(parent) = &visit.parent {
+            path.push(parent.clone());
+            visit = self.visits.get(parent)?;
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
+/// Breadth first search, see [`bfs`].
+pub struct Bfs<N, S, M = HashMap<N, Visit<N, usize>>> {
+    successors: S,
+    queue: VecDeque<(N, usize)>,
+    tree: Tree<N, usize, M>,
+}
+
+/// Visits the nodes reachable from the start ones in the order of the number of steps to
+/// them.
+pub fn bfs<N, S>(starts: impl IntoIterator<Item = N>, successors: S) -> Bfs<N, S>
+where
+    N: Clone + Eq + Hash,
+    S: Successors<N>,
+{
+    bfs_in(HashMap::new(), starts, successors)
+}
+
+/// The same as [`bfs`], keeping the visits in the given storage.
+pub fn bfs_in<N, S, M>(
+    visits: M,
+    starts: impl IntoIterator<Item = N>,
+    successors: S,
+) -> Bfs<N, S, M>
+where
+    N: Clone,
+    S: Successors<N>,
+    M: Visits<N, usize>,
+{
+    let mut tree = Tree::new(visits);
+    let queue = starts
+        .into_iter()
+        .filter(|node| tree.start(node.clone()))
+        .map(|node| (node, 0))
+        .collect();
+    Bfs {
+        successors,
+        queue,
+        tree,
+    }
+}
+
+impl<N: Clone, S: Successors<N>, M: Visits<N, usize>> Bfs<N, S, M> {
+    /// Records the predecessors of the nodes, so [`Tree::path`] can be used.
+    pub fn with_paths(mut self) -> Self {
+        self.tree.paths = true;
+        self
+    }
+
+    pub fn tree(&self) -> &Tree<N, usize, M> {
+        &self.tree
+    }
+
+    /// Visits all the remaining nodes and returns the tree.
+    pub fn into_tree(mut self) -> Tree<N, usize, M> {
+        self.by_ref().for_each(drop);
+        self.tree
+    }
+}
+
+impl<N: Clone, S: Successors<N>, M: Visits<N, usize>> Iterator for Bfs<N, S, M> {
+    type Item = (N, usize);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let (node, steps) = self.queue.pop_front()?;
+        for next in self.successors.successors(&node) {
+            if self.tree.reach_first(next.clone(), steps + 1, &node) {
+                self.queue.push_back((next, steps + 1));
+            }
+        }
+        Some((node, steps))
+    }
+}
+
+/// Entry of the priority queue, the one with the lowest priority goes first.
+struct Queued<N, C> {
+    priority: C,
+    cost: C,
+    node: N,
+}
+
+impl<N, C: Ord> PartialEq for Queued<N, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority == other.priority
+    }
+}
+
+impl<N, C: Ord> Eq for Queued<N, C> {}
+
+impl<N, C: Ord> PartialOrd for Queued<N, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<N, C: Ord> Ord for Queued<N, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.priority.cmp(&self.priority)
+    }
+}
+
+/// Dijkstra's search, see [`dijkstra`].
+pub struct Dijkstra<N, C, S, M = HashMap<N, Visit<N, C>>> {
+    successors: S,
+    queue: BinaryHeap<Queued<N, C>>,
+    tree: Tree<N, C, M>,
+}
+
+/// Visits the nodes reachable from the start ones in the order of the cost of the cheapest
+/// paths to them, the costs of the steps must not be negative.
+pub fn dijkstra<N, C, S>(starts: impl IntoIterator<Item = N>, successors: S) -> Dijkstra<N, C, S>
+where
+    N: Clone + Eq + Hash,
+    C: Cost,
+    S: WeightedSuccessors<N, C>,
+{
+    dijkstra_in(HashMap::new(), starts, successors)
+}
+
+/// The same as [`dijkstra`], keeping the visits in the given storage.
+pub fn dijkstra_in<N, C, S, M>(
+    visits: M,
+    starts: impl IntoIterator<Item = N>,
+    successors: S,
+) -> Dijkstra<N, C, S, M>
+where
+    N: Clone,
+    C: Cost,
+    S: WeightedSuccessors<N, C>,
+    M: Visits<N, C>,
+{
+    let mut tree = Tree::new(visits);
+    let queue = starts
+        .into_iter()
+        .filter(|node| tree.start(node.clone()))
+        .map(|node| Queued {
+            priority: C::default(),
+            cost: C::default(),
+            node,
+        })
+        .collect();
+    Dijkstra {
+        successors,
+        queue,
+        tree,
+    }
+}
+
+impl<N, C, S, M> Dijkstra<N, C, S, M>
+where
+    N: Clone,
+    C: Cost,
+    S: WeightedSuccessors<N, C>,
+    M: Visits<N, C>,
+{
+    /// Records the predecessors of the nodes, so [`Tree::path`] can be used.
+    pub fn with_paths(mut self) -> Self {
+        self.tree.paths = true;
+        self
+    }
+
+    /// Returns the nodes reached so far, the costs are final only for the visited ones.
+    pub fn tree(&self) -> &Tree<N, C, M> {
+        &self.tree
+    }
+
+    /// Visits all the remaining nodes and returns the tree.
+    pub fn into_tree(mut self) -> Tree<N, C, M> {
+        self.by_ref().for_each(drop);
+        self.tree
+    }
+}
+
+impl<N, C, S, M> Iterator for Dijkstra<N, C, S, M>
+where
+    N: Clone,
+    C: Cost,
+    S: WeightedSuccessors<N, C>,
+    M: Visits<N, C>,
+{
+    type Item = (N, C);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let Queued { cost, node, .. } = self.queue.pop()?;
+            if !self.tree.settle(&node, cost) {
+                continue;
+            }
+            for (next, step) in self.successors.successors(&node) {
+                let next_cost = cost + step;
+                if self.tree.reach(next.clone(), next_cost, Some(&node)) {
+                    self.queue.push(Queued {
+                        priority: next_cost,
+                        cost: next_cost,
+                        node: next,
+                    });
+                }
+            }
+            return Some((node, cost));
+        }
+    }
+}
+
+/// Finds the cheapest path from the start to a goal node and its cost. The heuristic
+/// estimates the cost of the rest of the path from a node, it must never overestimate it
+/// and must not decrease by more than the cost of a step.
+pub fn astar<N, C, S>(
+    start: N,
+    successors: S,
+    heuristic: impl Fn(&N) -> C,
+    is_goal: impl Fn(&N) -> bool,
+) -> Option<(Vec<N>, C)>
+where
+    N: Clone + Eq + Hash,
+    C: Cost,
+    S: WeightedSuccessors<N, C>,
+{
+    let mut tree = Tree::new(HashMap::new());
+    tree.paths = true;
+    tree.start(start.clone());
+    let mut queue = BinaryHeap::from([Queued {
+        priority: heuristic(&start),
+        cost: C::default(),
+        node: start,
+    }]);
+    while let Some(Queued { cost, node, .. }) = queue.pop() {
+        if !tree.settle(&node, cost) {
+            continue;
+        }
+        if is_goal(&node) {
+            return Some((tree.path(&node)?, cost));
+        }
+        for (next, step) in successors.successors(&node) {
+            let next_cost = cost + step;
+            if tree.reach(next.clone(), next_cost, Some(&node)) {
+                queue.push(Queued {
+                    priority: next_cost + heuristic(&next),
+                    cost: next_cost,
+                    node: next,
+                });
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAZE: &str = "\
+S..#....
+.#.#.##.
+.#...#..
+.####.#.
+....#.#E";
+
+    fn maze() -> (Grid<bool>, Pos, Pos) {
+        let grid = Grid::parse(MAZE, |_, ch| Some(ch != '#')).unwrap();
+        let start = grid.position(|_| true).unwrap();
+        let end = (grid.rows() - 1, grid.cols() - 1);
+        (grid, start, end)
+    }
+
+    #[test]
+    fn test_bfs() {
+        let (grid, start, end) = maze();
+        let successors = |pos: &Pos| grid.neighbours4(*pos).filter(|next| grid[*next]);
+        let mut search = bfs([start], successors).with_paths();
+        assert_eq!(search.next(), Some((start, 0)));
+        assert_eq!(search.find(|(pos, _)| *pos == end), Some((end, 15)));
+        let path = search.tree().path(&end).unwrap();
+        assert_eq!(path.len(), 16);
+        assert_eq!((path[0], path[15]), (start, end));
+        assert!(path
+            .windows(2)
+            .all(|w| grid.neighbours4(w[0]).any(|n| n == w[1])));
+
+        let tree = bfs([start], successors).into_tree();
+        assert_eq!(
+            tree.len(),
+            grid.iter().filter(|(_, &open)| open).count() - 2
+        );
+        assert_eq!(tree.cost(&(3, 5)), None);
+        assert_eq!(tree.cost(&end), Some(15));
+
+        let visits = Grid::new(grid.rows(), grid.cols(), None);
+        let grid_tree = bfs_in(visits, [start], successors).into_tree();
+        assert_eq!(grid_tree.len(), tree.len());
+        assert!(grid_tree
+            .costs()
+            .all(|(pos, cost)| tree.cost(&pos) == Some(cost)));
+    }
+
+    #[test]
+    fn test_bfs_many_starts() {
+        let tree = bfs([0, 10, 0], |n: &i32| {
+            [n - 1, n + 1].into_iter().filter(|n| (0..=10).contains(n))
+        })
+        .into_tree();
+        assert_eq!(tree.cost(&5), Some(5));
+        assert_eq!(tree.cost(&7), Some(3));
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        // going through b is longer but cheaper
+        let edges = |node: &char| match node {
+            'a' => vec![('b', 1), ('c', 5), ('d', 9)],
+            'b' => vec![('c', 1)],
+            'c' => vec![('d', 2)],
+            _ => vec![],
+        };
+        let order = dijkstra(['a'], edges).collect::<Vec<_>>();
+        assert_eq!(order, vec![('a', 0), ('b', 1), ('c', 2), ('d', 4)]);
+        let tree = dijkstra(['a'], edges).with_paths().into_tree();
+        assert_eq!(tree.path(&'d'), Some(vec!['a', 'b', 'c', 'd']));
+        assert_eq!(tree.path(&'e'), None);
+    }
+
+    #[test]
+    fn test_astar() {
+        let (grid, start, end) = maze();
+        let successors = |pos: &Pos| {
+            grid.neighbours4(*pos)
+                .filter(|next| grid[*next])
+                .map(|next| (next, 1))
+                .collect::<Vec<_>>()
+        };
+        let distance = |pos: &Pos| pos.0.abs_diff(end.0) + pos.1.abs_diff(end.1);
+        let (path, cost) = astar(start, successors, distance, |pos| *pos == end).unwrap();
+        assert_eq!(cost, 15);
+        assert_eq!(path.len(), 16);
+        assert_eq!(
+            astar(start, successors, distance, |pos| *pos == (3, 5)),
+            None
+        );
+    }
+}
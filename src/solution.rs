@@ -0,0 +1,99 @@
+mod answer;
+mod bench;
+pub mod cancel;
+mod params;
+
+pub use answer::Answer;
+pub use bench::BenchSettings;
+pub use cancel::Cancel;
+pub use params::{Parameter, Params};
+
+use crate::{
+    animation::Frames,
+    input::{self, Normalization},
+    parsing::ParseError,
+    render::Image,
+};
+
+pub trait Solution: Sync {
+    fn year(&self) -> u16;
+    fn day(&self) -> u8;
+    fn title(&self) -> &'static str;
+
+    fn part1(&self, file_content: &str, params: &Params) -> Result<Answer, ParseError>;
+    fn part2(&self, file_content: &str, params: &Params) -> Result<Answer, ParseError>;
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[]
+    }
+
+    /// Returns the default values of the parameters.
+    fn params(&self) -> Params {
+        Params::new(self.parameters())
+    }
+
+    fn bench_settings(&self) -> BenchSettings {
+        BenchSettings::default()
+    }
+
+    fn normalization(&self) -> Normalization {
+        Normalization::default()
+    }
+
+    /// Draws the picture of the puzzle, e.g. the final state of its simulation. Returns
+    /// `None` if the puzzle has nothing to show.
+    fn image(&self, _file_content: &str, _params: &Params) -> Option<Result<Image, ParseError>> {
+        None
+    }
+
+    /// Draws the picture of the normalized input, see [`Solution::image`].
+    fn render(&self, file_content: &str, params: &Params) -> Option<Result<Image, ParseError>> {
+        let file_content = &input::normalize(file_content, self.normalization());
+        let image = self.image(file_content, params)?;
+        Some(image.map_err(|e| e.in_puzzle(self.year(), self.day())))
+    }
+
+    /// Starts the simulation of the puzzle to be watched step by step. Returns `None` if the
+    /// puzzle has nothing to show.
+    fn frames(
+        &self,
+        _file_content: &str,
+        _params: &Params,
+    ) -> Option<Result<Box<dyn Frames>, ParseError>> {
+        None
+    }
+
+    /// Starts the simulation of the normalized input, see [`Solution::frames`].
+    fn animate(
+        &self,
+        file_content: &str,
+        params: &Params,
+    ) -> Option<Result<Box<dyn Frames>, ParseError>> {
+        let file_content = &input::normalize(file_content, self.normalization());
+        let frames = self.frames(file_content, params)?;
+        Some(frames.map_err(|e| e.in_puzzle(self.year(), self.day())))
+    }
+
+    /// Solves the given part (counting from 1) of the normalized input, returns `None` if
+    /// there is no such part.
+    fn solve(
+        &self,
+        part: u8,
+        file_content: &str,
+        params: &Params,
+    ) -> Option<Result<Answer, ParseError>> {
+        let file_content = &input::normalize(file_content, self.normalization());
+        let answer = match part {
+            1 => self.part1(file_content, params),
+            2 => self.part2(file_content, params),
+            _ => return None,
+        };
+        Some(answer.map_err(|e| e.in_puzzle(self.year(), self.day())))
+    }
+}
+
+impl std::fmt::Debug for dyn Solution {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} day {}: {}", self.year(), self.day(), self.title())
+    }
+}
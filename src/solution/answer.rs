@@ -0,0 +1,73 @@
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Answer {
+    Integer(i128),
+    Text(String),
+    /// Multi-line picture, e.g. the CRT screen of 2022 day 10.
+    Art(String),
+}
+
+impl Answer {
+    pub fn is_multiline(&self) -> bool {
+        matches!(self, Answer::Art(_))
+    }
+}
+
+impl std::fmt::Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Integer(i) => write!(f, "{i}"),
+            Answer::Text(s) => write!(f, "{s}"),
+            Answer::Art(s) => write!(f, "{s}"),
+        }
+    }
+}
+
+macro_rules! impl_from_integer {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(value: $t) -> Self {
+                    Answer::Integer(value as i128)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_integer!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        if value.trim_end().contains('\n') {
+            Answer::Art(value)
+        } else {
+            Answer::Text(value)
+        }
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        value.to_owned().into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_integers() {
+        assert_eq!(Answer::from(5u32), Answer::Integer(5));
+        assert_eq!(Answer::from(-5i32), Answer::Integer(-5));
+        assert_eq!(Answer::from(u64::MAX).to_string(), u64::MAX.to_string());
+    }
+
+    #[test]
+    fn test_from_strings() {
+        assert_eq!(Answer::from("CMZ"), Answer::Text("CMZ".to_owned()));
+        assert!(!Answer::from("CMZ\n").is_multiline());
+        assert!(Answer::from("#.\n.#\n").is_multiline());
+        assert_eq!(Answer::from("#.\n.#\n").to_string(), "#.\n.#\n");
+    }
+}
@@ -0,0 +1,17 @@
+use std::time::Duration;
+
+/// How the puzzle is measured by `cargo bench`, the defaults are the ones of criterion.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BenchSettings {
+    pub sample_size: usize,
+    pub measurement_time: Duration,
+}
+
+impl Default for BenchSettings {
+    fn default() -> Self {
+        Self {
+            sample_size: 100,
+            measurement_time: Duration::from_secs(5),
+        }
+    }
+}
@@ -0,0 +1,89 @@
+use std::{
+    panic,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::{self, RecvTimeoutError},
+        Arc,
+    },
+    thread,
+    time::Duration,
+};
+
+/// Token for cooperative cancellation of a long-running solution. The solution calls
+/// [`Cancel::check`] in its loops, the runner cancels the token, e.g. after a timeout.
+#[derive(Debug, Clone, Default)]
+pub struct Cancel(Arc<AtomicBool>);
+
+/// Payload of the unwinding started by [`Cancel::check`] when the token is cancelled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cancelled;
+
+impl Cancel {
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    /// Stops the solution if the token is cancelled. It unwinds with [`Cancelled`] payload
+    /// without calling the panic hook, so the runner can tell it from a panic.
+    pub fn check(&self) {
+        if self.is_cancelled() {
+            panic::resume_unwind(Box::new(Cancelled));
+        }
+    }
+}
+
+/// Runs `f` with a token which is cancelled once `timeout` passes, `None` means no timeout.
+pub fn with_timeout<T>(timeout: Option<Duration>, f: impl FnOnce(&Cancel) -> T) -> T {
+    let cancel = Cancel::default();
+    let Some(timeout) = timeout else {
+        return f(&cancel);
+    };
+    thread::scope(|scope| {
+        let (done, finished) = mpsc::channel::<()>();
+        let watched = &cancel;
+        scope.spawn(move || {
+            if finished.recv_timeout(timeout) == Err(RecvTimeoutError::Timeout) {
+                watched.cancel();
+            }
+        });
+        let res = f(&cancel);
+        drop(done);
+        res
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check() {
+        let cancel = Cancel::default();
+        cancel.check();
+        cancel.clone().cancel();
+        assert!(cancel.is_cancelled());
+        let payload = panic::catch_unwind(|| cancel.check()).unwrap_err();
+        assert_eq!(payload.downcast_ref::<Cancelled>(), Some(&Cancelled));
+    }
+
+    #[test]
+    fn test_with_timeout() {
+        assert!(!with_timeout(None, |cancel| cancel.is_cancelled()));
+        let finished = with_timeout(Some(Duration::from_secs(60)), |cancel| {
+            cancel.is_cancelled()
+        });
+        assert!(!finished);
+        let payload = panic::catch_unwind(|| {
+            with_timeout(Some(Duration::from_millis(10)), |cancel| loop {
+                cancel.check();
+                thread::yield_now();
+            })
+        })
+        .unwrap_err();
+        assert!(payload.is::<Cancelled>());
+    }
+}
@@ -0,0 +1,157 @@
+use super::Cancel;
+use crate::trace::Trace;
+use std::str::FromStr;
+
+/// A knob of a puzzle that is not part of the input, e.g. the row to scan in 2022 day 15.
+#[derive(Debug, Clone, Copy)]
+pub struct Parameter {
+    pub name: &'static str,
+    pub default: &'static str,
+    is_valid: fn(&str) -> bool,
+}
+
+impl Parameter {
+    /// Declares a parameter whose values are parsed as `T`.
+    pub const fn new<T: FromStr>(name: &'static str, default: &'static str) -> Self {
+        Self {
+            name,
+            default,
+            is_valid: is_valid::<T>,
+        }
+    }
+
+    pub fn is_valid(&self, value: &str) -> bool {
+        (self.is_valid)(value)
+    }
+}
+
+fn is_valid<T: FromStr>(value: &str) -> bool {
+    value.parse::<T>().is_ok()
+}
+
+/// Values of the parameters of a solution, the defaults are used unless they are overridden.
+/// It also carries the token the long-running solutions check to stop early and the trace
+/// the solutions emit their events into.
+#[derive(Debug, Clone)]
+pub struct Params {
+    parameters: &'static [Parameter],
+    values: Vec<String>,
+    cancel: Cancel,
+    trace: Trace,
+}
+
+impl Params {
+    pub fn new(parameters: &'static [Parameter]) -> Self {
+        Self {
+            parameters,
+            values: parameters.iter().map(|p| p.default.to_owned()).collect(),
+            cancel: Cancel::default(),
+            trace: Trace::default(),
+        }
+    }
+
+    pub fn with_cancel(self, cancel: Cancel) -> Self {
+        Self { cancel, ..self }
+    }
+
+    pub fn cancel(&self) -> &Cancel {
+        &self.cancel
+    }
+
+    pub fn with_trace(self, trace: Trace) -> Self {
+        Self { trace, ..self }
+    }
+
+    pub fn trace(&self) -> &Trace {
+        &self.trace
+    }
+
+    /// Overrides the value of the parameter, checking that the solution knows it.
+    pub fn set(&mut self, name: &str, value: &str) -> Result<(), String> {
+        let ind = self
+            .index(name)
+            .ok_or_else(|| format!("unknown parameter '{name}'"))?;
+        if !self.parameters[ind].is_valid(value) {
+            return Err(format!("invalid value of parameter {name}: '{value}'"));
+        }
+        self.values[ind] = value.to_owned();
+        Ok(())
+    }
+
+    /// Overrides the parameter from `name=value` assignment.
+    pub fn assign(&mut self, assignment: &str) -> Result<(), String> {
+        let (name, value) = assignment
+            .split_once('=')
+            .ok_or_else(|| format!("expected name=value, found '{assignment}'"))?;
+        self.set(name.trim(), value.trim())
+    }
+
+    /// Returns the value of the parameter, which is expected to be declared with the type `T`.
+    pub fn get<T: FromStr>(&self, name: &str) -> T {
+        let ind = self
+            .index(name)
+            .unwrap_or_else(|| panic!("parameter '{name}' is not declared"));
+        match self.values[ind].parse() {
+            Ok(value) => value,
+            Err(_) => panic!("parameter '{name}' is declared with another type"),
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&'static str, &str)> + '_ {
+        self.parameters
+            .iter()
+            .zip(&self.values)
+            .map(|(p, value)| (p.name, value.as_str()))
+    }
+
+    fn index(&self, name: &str) -> Option<usize> {
+        self.parameters.iter().position(|p| p.name == name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PARAMETERS: &[Parameter] = &[
+        Parameter::new::<i32>("row", "2000000"),
+        Parameter::new::<usize>("width", "7"),
+    ];
+
+    #[test]
+    fn test_defaults() {
+        let params = Params::new(PARAMETERS);
+        assert_eq!(params.get::<i32>("row"), 2000000);
+        assert_eq!(params.get::<usize>("width"), 7);
+    }
+
+    #[test]
+    fn test_overrides() {
+        let mut params = Params::new(PARAMETERS);
+        params.assign("row=10").unwrap();
+        params.set("width", "9").unwrap();
+        assert_eq!(params.get::<i32>("row"), 10);
+        assert_eq!(
+            params.iter().collect::<Vec<_>>(),
+            vec![("row", "10"), ("width", "9")]
+        );
+    }
+
+    #[test]
+    fn test_invalid() {
+        let mut params = Params::new(PARAMETERS);
+        assert_eq!(
+            params.assign("height=1").unwrap_err(),
+            "unknown parameter 'height'"
+        );
+        assert_eq!(
+            params.set("width", "-1").unwrap_err(),
+            "invalid value of parameter width: '-1'"
+        );
+        assert_eq!(
+            params.assign("row").unwrap_err(),
+            "expected name=value, found 'row'"
+        );
+        assert_eq!(params.get::<usize>("width"), 7);
+    }
+}
@@ -0,0 +1,98 @@
+//! Structured events the solutions emit instead of printing, e.g. the cycle found by
+//! 2022 day 17. They are silent unless the runner attaches an [`Observer`]:
+//!
+//! ```
+//! use advent::trace::{Event, Trace};
+//! use std::sync::{Arc, Mutex};
+//!
+//! let events = Arc::new(Mutex::new(Vec::new()));
+//! let observed = events.clone();
+//! let trace = Trace::new(move |event: &Event| observed.lock().unwrap().push(event.to_string()));
+//! trace.event("cycle", &[("length", &1725), ("height", &2659)]);
+//! assert_eq!(*events.lock().unwrap(), vec!["cycle length=1725 height=2659"]);
+//! ```
+use std::{fmt::Display, sync::Arc};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Event {
+    pub name: &'static str,
+    pub fields: Vec<(&'static str, String)>,
+}
+
+impl std::fmt::Display for Event {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name)?;
+        for (name, value) in &self.fields {
+            write!(f, " {name}={value}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Receives the events, it is called from the threads the solutions run in.
+pub trait Observer: Send + Sync {
+    fn event(&self, event: &Event);
+}
+
+impl<F: Fn(&Event) + Send + Sync> Observer for F {
+    fn event(&self, event: &Event) {
+        self(event)
+    }
+}
+
+/// Handle the solutions emit the events into, the default one drops them.
+#[derive(Clone, Default)]
+pub struct Trace(Option<Arc<dyn Observer>>);
+
+impl Trace {
+    pub fn new(observer: impl Observer + 'static) -> Self {
+        Self(Some(Arc::new(observer)))
+    }
+
+    /// Checks whether the events are observed, so that costly ones can be skipped.
+    pub fn is_enabled(&self) -> bool {
+        self.0.is_some()
+    }
+
+    /// Emits the event, the fields are formatted only if it is observed.
+    pub fn event(&self, name: &'static str, fields: &[(&'static str, &dyn Display)]) {
+        if let Some(observer) = &self.0 {
+            observer.event(&Event {
+                name,
+                fields: fields
+                    .iter()
+                    .map(|(name, value)| (*name, value.to_string()))
+                    .collect(),
+            });
+        }
+    }
+}
+
+impl std::fmt::Debug for Trace {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let state = if self.is_enabled() {
+            "enabled"
+        } else {
+            "disabled"
+        };
+        write!(f, "Trace({state})")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_disabled() {
+        struct Unformattable;
+        impl Display for Unformattable {
+            fn fmt(&self, _: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                panic!("disabled trace formats the fields")
+            }
+        }
+        let trace = Trace::default();
+        assert!(!trace.is_enabled());
+        trace.event("event", &[("field", &Unformattable)]);
+    }
+}
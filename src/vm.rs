@@ -0,0 +1,357 @@
+//! Virtual machine for the assembly-like puzzles. The instruction set defines the registers,
+//! how many cycles every instruction takes and what it does; the machine steps the program
+//! cycle by cycle, so the registers can be read during every cycle.
+//!
+//! ```
+//! use advent::vm::{disassemble, InstructionSet, Registers, Vm};
+//! use std::fmt;
+//!
+//! #[derive(Debug, Clone, Copy)]
+//! enum Op {
+//!     Inc,
+//!     Double,
+//! }
+//!
+//! impl fmt::Display for Op {
+//!     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+//!         write!(f, "{}", if let Op::Inc = self { "inc" } else { "double" })
+//!     }
+//! }
+//!
+//! struct Toy;
+//!
+//! impl InstructionSet for Toy {
+//!     type Instruction = Op;
+//!     const REGISTERS: &'static [(&'static str, i64)] = &[("a", 1)];
+//!
+//!     fn cycles(op: &Op) -> usize {
+//!         if let Op::Inc = op { 1 } else { 3 }
+//!     }
+//!
+//!     fn execute(op: &Op, registers: &mut Registers) -> isize {
+//!         match op {
+//!             Op::Inc => registers["a"] += 1,
+//!             Op::Double => registers["a"] *= 2,
+//!         }
+//!         1
+//!     }
+//! }
+//!
+//! let mut vm = Vm::<Toy>::new(vec![Op::Inc, Op::Double]);
+//! let mut values = Vec::new();
+//! while vm.step().is_some() {
+//!     values.push(vm.registers()["a"]);
+//! }
+//! // the result of an instruction is seen after its last cycle
+//! assert_eq!(values, vec![1, 2, 2, 2]);
+//! assert_eq!(vm.registers()["a"], 4);
+//! assert_eq!(disassemble::<Toy>(&[Op::Inc, Op::Double]), "0  inc     1 cycle\n1  double  3 cycles\n");
+//! ```
+use std::{
+    fmt::Display,
+    ops::{Index, IndexMut},
+};
+
+use crate::trace::Trace;
+
+pub trait InstructionSet {
+    type Instruction: Display;
+
+    /// Names of the registers with their initial values.
+    const REGISTERS: &'static [(&'static str, i64)];
+
+    /// Number of the cycles the instruction takes, it is at least one.
+    fn cycles(instruction: &Self::Instruction) -> usize;
+
+    /// Applies the instruction at the end of its last cycle. Returns the offset of the next
+    /// instruction, so it is 1 unless the instruction jumps.
+    fn execute(instruction: &Self::Instruction, registers: &mut Registers) -> isize;
+}
+
+/// Values of the registers, indexed by their names.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Registers {
+    names: &'static [(&'static str, i64)],
+    values: Vec<i64>,
+}
+
+impl Registers {
+    fn new(names: &'static [(&'static str, i64)]) -> Self {
+        Self {
+            names,
+            values: names.iter().map(|&(_, value)| value).collect(),
+        }
+    }
+
+    fn position(&self, name: &str) -> usize {
+        self.names
+            .iter()
+            .position(|&(n, _)| n == name)
+            .unwrap_or_else(|| panic!("there is no register {name:?}"))
+    }
+}
+
+impl Index<&str> for Registers {
+    type Output = i64;
+
+    fn index(&self, name: &str) -> &i64 {
+        &self.values[self.position(name)]
+    }
+}
+
+impl IndexMut<&str> for Registers {
+    fn index_mut(&mut self, name: &str) -> &mut i64 {
+        let ind = self.position(name);
+        &mut self.values[ind]
+    }
+}
+
+impl Display for Registers {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (ind, (&(name, _), value)) in self.names.iter().zip(&self.values).enumerate() {
+            if ind > 0 {
+                write!(f, " ")?;
+            }
+            write!(f, "{name}={value}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Condition [`Vm::run`] stops on, it is checked during every cycle.
+pub enum Breakpoint {
+    /// Stops during the cycle with the number, counting from 1.
+    Cycle(usize),
+    /// Stops during every cycle the register matches the condition.
+    Register(&'static str, Box<dyn Fn(i64) -> bool>),
+}
+
+impl Breakpoint {
+    pub fn register(name: &'static str, condition: impl Fn(i64) -> bool + 'static) -> Self {
+        Breakpoint::Register(name, Box::new(condition))
+    }
+
+    fn is_hit(&self, cycle: usize, registers: &Registers) -> bool {
+        match self {
+            Breakpoint::Cycle(at) => *at == cycle,
+            Breakpoint::Register(name, condition) => condition(registers[name]),
+        }
+    }
+}
+
+/// Why [`Vm::run`] stopped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stop {
+    Halted,
+    /// The index of the breakpoint in the order they were added.
+    Breakpoint(usize),
+}
+
+pub struct Vm<S: InstructionSet> {
+    program: Vec<S::Instruction>,
+    registers: Registers,
+    pc: usize,
+    cycle: usize,
+    /// Cycles spent on the current instruction.
+    elapsed: usize,
+    breakpoints: Vec<Breakpoint>,
+    trace: Trace,
+}
+
+impl<S: InstructionSet> Vm<S> {
+    pub fn new(program: Vec<S::Instruction>) -> Self {
+        Self {
+            program,
+            registers: Registers::new(S::REGISTERS),
+            pc: 0,
+            cycle: 0,
+            elapsed: 0,
+            breakpoints: Vec::new(),
+            trace: Trace::default(),
+        }
+    }
+
+    /// Emits a "cycle" event with the instruction and the registers during every cycle.
+    pub fn with_trace(mut self, trace: Trace) -> Self {
+        self.trace = trace;
+        self
+    }
+
+    pub fn registers(&self) -> &Registers {
+        &self.registers
+    }
+
+    pub fn registers_mut(&mut self) -> &mut Registers {
+        &mut self.registers
+    }
+
+    /// Number of the current cycle counting from 1, 0 before the first one.
+    pub fn cycle(&self) -> usize {
+        self.cycle
+    }
+
+    /// Address of the current instruction.
+    pub fn pc(&self) -> usize {
+        self.pc
+    }
+
+    /// Returns the index of the breakpoint.
+    pub fn add_breakpoint(&mut self, breakpoint: Breakpoint) -> usize {
+        self.breakpoints.push(breakpoint);
+        self.breakpoints.len() - 1
+    }
+
+    /// Starts the next cycle and returns its number, or `None` if the program has halted.
+    /// The registers hold the values during the cycle, the current instruction is applied
+    /// when the following cycle starts.
+    pub fn step(&mut self) -> Option<usize> {
+        if self.elapsed > 0 && self.elapsed == S::cycles(&self.program[self.pc]) {
+            let offset = S::execute(&self.program[self.pc], &mut self.registers);
+            self.pc = self.pc.checked_add_signed(offset).unwrap_or(usize::MAX);
+            self.elapsed = 0;
+        }
+        let instruction = self.program.get(self.pc)?;
+        self.elapsed += 1;
+        self.cycle += 1;
+        self.trace.event(
+            "cycle",
+            &[
+                ("cycle", &self.cycle),
+                ("pc", &self.pc),
+                ("instruction", instruction),
+                ("registers", &self.registers),
+            ],
+        );
+        Some(self.cycle)
+    }
+
+    /// Steps the program until it halts or a breakpoint is hit.
+    pub fn run(&mut self) -> Stop {
+        while let Some(cycle) = self.step() {
+            let hit = self
+                .breakpoints
+                .iter()
+                .position(|breakpoint| breakpoint.is_hit(cycle, &self.registers));
+            if let Some(ind) = hit {
+                return Stop::Breakpoint(ind);
+            }
+        }
+        Stop::Halted
+    }
+}
+
+/// Lists the program one instruction per line with its address and its cycles.
+pub fn disassemble<S: InstructionSet>(program: &[S::Instruction]) -> String {
+    let texts = program.iter().map(ToString::to_string).collect::<Vec<_>>();
+    let address_width = program.len().saturating_sub(1).to_string().len();
+    let width = texts.iter().map(String::len).max().unwrap_or_default();
+    let mut res = String::new();
+    for (address, (instruction, text)) in program.iter().zip(&texts).enumerate() {
+        let cycles = S::cycles(instruction);
+        let unit = if cycles == 1 { "cycle" } else { "cycles" };
+        res += &format!("{address:>address_width$}  {text:<width$}  {cycles} {unit}\n");
+    }
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Counts down `n` to 0, adding `n` to `sum` on the way.
+    #[derive(Debug, Clone, Copy)]
+    enum Op {
+        Add,
+        Dec,
+        /// Jumps by the offset if `n` is not 0.
+        Jnz(isize),
+    }
+
+    impl Display for Op {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                Op::Add => write!(f, "add sum n"),
+                Op::Dec => write!(f, "dec n"),
+                Op::Jnz(offset) => write!(f, "jnz n {offset}"),
+            }
+        }
+    }
+
+    struct Counter;
+
+    impl InstructionSet for Counter {
+        type Instruction = Op;
+        const REGISTERS: &'static [(&'static str, i64)] = &[("n", 3), ("sum", 0)];
+
+        fn cycles(op: &Op) -> usize {
+            match op {
+                Op::Add => 2,
+                Op::Dec | Op::Jnz(_) => 1,
+            }
+        }
+
+        fn execute(op: &Op, registers: &mut Registers) -> isize {
+            match *op {
+                Op::Add => registers["sum"] += registers["n"],
+                Op::Dec => registers["n"] -= 1,
+                Op::Jnz(offset) if registers["n"] != 0 => return offset,
+                Op::Jnz(_) => {}
+            }
+            1
+        }
+    }
+
+    const PROGRAM: [Op; 3] = [Op::Add, Op::Dec, Op::Jnz(-2)];
+
+    #[test]
+    fn test_run() {
+        let mut vm = Vm::<Counter>::new(PROGRAM.to_vec());
+        assert_eq!(vm.run(), Stop::Halted);
+        assert_eq!(vm.registers()["sum"], 6);
+        assert_eq!(vm.registers().to_string(), "n=0 sum=6");
+        assert_eq!(vm.cycle(), 12);
+        assert_eq!(vm.step(), None);
+    }
+
+    #[test]
+    fn test_breakpoints() {
+        let mut vm = Vm::<Counter>::new(PROGRAM.to_vec());
+        vm.add_breakpoint(Breakpoint::Cycle(5));
+        vm.add_breakpoint(Breakpoint::register("sum", |sum| sum >= 5));
+        assert_eq!(vm.run(), Stop::Breakpoint(0));
+        assert_eq!((vm.cycle(), vm.pc()), (5, 0));
+        // the addition of 2 is applied at the end of the cycle 6
+        assert_eq!(vm.run(), Stop::Breakpoint(1));
+        assert_eq!((vm.cycle(), vm.registers()["sum"]), (7, 5));
+        assert_eq!(vm.run(), Stop::Breakpoint(1));
+        vm.registers_mut()["n"] = 0;
+        assert_eq!(vm.run(), Stop::Halted);
+    }
+
+    #[test]
+    fn test_trace() {
+        use std::sync::{Arc, Mutex};
+
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let observed = events.clone();
+        let trace = Trace::new(move |event: &crate::trace::Event| {
+            observed.lock().unwrap().push(event.to_string())
+        });
+        Vm::<Counter>::new(PROGRAM.to_vec()).with_trace(trace).run();
+        let events = events.lock().unwrap();
+        assert_eq!(events.len(), 12);
+        assert_eq!(
+            events[3],
+            "cycle cycle=4 pc=2 instruction=jnz n -2 registers=n=2 sum=3"
+        );
+    }
+
+    #[test]
+    fn test_disassemble() {
+        assert_eq!(
+            disassemble::<Counter>(&PROGRAM),
+            "0  add sum n  2 cycles\n1  dec n      1 cycle\n2  jnz n -2   1 cycle\n"
+        );
+        assert_eq!(disassemble::<Counter>(&[]), "");
+    }
+}
@@ -1,3 +1,9 @@
+use crate::{
+    parsing::ParseError,
+    registry::Registry,
+    solution::{Answer, Params, Solution},
+};
+
 pub fn solve_task1(file_content: &str) -> i32 {
     file_content
         .chars()
@@ -36,10 +42,34 @@ pub fn solve_task2(file_content: &str) -> usize {
         }
     }
 }
+
+pub struct Day;
+
+impl Solution for Day {
+    fn year(&self) -> u16 {
+        2015
+    }
+    fn day(&self) -> u8 {
+        1
+    }
+    fn title(&self) -> &'static str {
+        "Not Quite Lisp"
+    }
+    fn part1(&self, file_content: &str, _params: &Params) -> Result<Answer, ParseError> {
+        Ok(solve_task1(file_content).into())
+    }
+    fn part2(&self, file_content: &str, _params: &Params) -> Result<Answer, ParseError> {
+        Ok(solve_task2(file_content).into())
+    }
+}
+
+pub fn register(registry: &mut Registry) {
+    registry.register(&Day);
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    const ACTUAL: &str = include_str!("../benches/y15d01.txt");
     #[test]
     #[ignore]
     fn test_task1() {
@@ -49,22 +79,10 @@ mod tests {
         assert_eq!(format!("{}", solve_task1("(()(()(")), "3");
     }
 
-    #[test]
-    #[ignore]
-    fn test_task1_actual() {
-        assert_eq!(format!("{}", solve_task1(ACTUAL)), "280");
-    }
-
     #[test]
     #[ignore]
     fn test_task2() {
         assert_eq!(format!("{}", solve_task2(")")), "1");
         assert_eq!(format!("{}", solve_task2("()())")), "5");
     }
-
-    #[test]
-    #[ignore]
-    fn test_task2_actual() {
-        assert_eq!(format!("{}", solve_task2(ACTUAL)), "1797");
-    }
 }
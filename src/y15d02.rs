@@ -1,60 +1,81 @@
-use nom::{self, bytes::complete, character, multi::separated_list1, IResult};
+use crate::{
+    parsing::{parse_lines, tag, IResult, ParseError},
+    registry::Registry,
+    solution::{Answer, Params, Solution},
+};
+use nom::{
+    character::complete,
+    sequence::{preceded, tuple},
+};
 
-pub fn solve<F>(file_content: &str, f: F) -> u32
+pub fn solve<F>(file_content: &str, f: F) -> Result<u32, ParseError>
 where
     F: Fn((u32, u32, u32)) -> u32,
 {
-    file_content
-        .lines()
-        .map(|line| parse_dimensions(line).unwrap().1)
-        .map(f)
+    parse_lines(file_content, parse_dimensions)
+        .map(|dimensions| dimensions.map(&f))
         .sum()
 }
 
-pub fn solve_task1(file_content: &str) -> u32 {
+pub fn solve_task1(file_content: &str) -> Result<u32, ParseError> {
     solve(file_content, |(l, w, h)| {
         2 * l * w + 2 * w * h + 2 * l * h + l * w
     })
 }
-pub fn solve_task2(file_content: &str) -> u32 {
+pub fn solve_task2(file_content: &str) -> Result<u32, ParseError> {
     solve(file_content, |(l, w, h)| 2 * (l + w) + l * w * h)
 }
 
-fn parse_dimensions(line: &str) -> IResult<&str, (u32, u32, u32)> {
-    let sep = complete::tag("x");
-    let dimension = character::complete::u32;
-    let mut parse = separated_list1(sep, dimension);
-    let (input, mut v) = parse(line)?;
+fn parse_dimensions(line: &str) -> IResult<'_, (u32, u32, u32)> {
+    let mut parse = tuple((
+        complete::u32,
+        preceded(tag("x"), complete::u32),
+        preceded(tag("x"), complete::u32),
+    ));
+    let (input, (l, w, h)) = parse(line)?;
+    let mut v = [l, w, h];
     v.sort();
     Ok((input, (v[0], v[1], v[2])))
 }
+
+pub struct Day;
+
+impl Solution for Day {
+    fn year(&self) -> u16 {
+        2015
+    }
+    fn day(&self) -> u8 {
+        2
+    }
+    fn title(&self) -> &'static str {
+        "I Was Told There Would Be No Math"
+    }
+    fn part1(&self, file_content: &str, _params: &Params) -> Result<Answer, ParseError> {
+        Ok(solve_task1(file_content)?.into())
+    }
+    fn part2(&self, file_content: &str, _params: &Params) -> Result<Answer, ParseError> {
+        Ok(solve_task2(file_content)?.into())
+    }
+}
+
+pub fn register(registry: &mut Registry) {
+    registry.register(&Day);
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     const INPUT: &str = "2x3x4
 1x1x10";
-    const ACTUAL: &str = include_str!("../benches/y15d02.txt");
     #[test]
     #[ignore]
     fn test_task1() {
-        assert_eq!(format!("{}", solve_task1(INPUT)), "101");
-    }
-
-    #[test]
-    #[ignore]
-    fn test_task1_actual() {
-        assert_eq!(format!("{}", solve_task1(ACTUAL)), "1606483");
+        assert_eq!(format!("{}", solve_task1(INPUT).unwrap()), "101");
     }
 
     #[test]
     #[ignore]
     fn test_task2() {
-        assert_eq!(format!("{}", solve_task2(INPUT)), "48");
-    }
-
-    #[test]
-    #[ignore]
-    fn test_task2_actual() {
-        assert_eq!(format!("{}", solve_task2(ACTUAL)), "3842356");
+        assert_eq!(format!("{}", solve_task2(INPUT).unwrap()), "48");
     }
 }
@@ -1,10 +1,15 @@
+use crate::{
+    parsing::ParseError,
+    registry::Registry,
+    solution::{Answer, Params, Solution},
+};
 use std::collections::HashSet;
 
-pub fn solve_task1(file_content: &str) -> impl std::fmt::Display {
+pub fn solve_task1(file_content: &str) -> usize {
     solve::<1>(file_content)
 }
 
-pub fn solve_task2(file_content: &str) -> impl std::fmt::Display {
+pub fn solve_task2(file_content: &str) -> usize {
     solve::<2>(file_content)
 }
 
@@ -37,10 +42,34 @@ pub fn solve<const N: usize>(file_content: &str) -> usize {
     }
     s.len()
 }
+
+pub struct Day;
+
+impl Solution for Day {
+    fn year(&self) -> u16 {
+        2015
+    }
+    fn day(&self) -> u8 {
+        3
+    }
+    fn title(&self) -> &'static str {
+        "Perfectly Spherical Houses in a Vacuum"
+    }
+    fn part1(&self, file_content: &str, _params: &Params) -> Result<Answer, ParseError> {
+        Ok(solve_task1(file_content).into())
+    }
+    fn part2(&self, file_content: &str, _params: &Params) -> Result<Answer, ParseError> {
+        Ok(solve_task2(file_content).into())
+    }
+}
+
+pub fn register(registry: &mut Registry) {
+    registry.register(&Day);
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    const ACTUAL: &str = include_str!("../benches/y15d03.txt");
 
     #[test]
     #[ignore]
@@ -50,12 +79,6 @@ mod tests {
         assert_eq!(format!("{}", solve_task1("^v^v^v^v^v")), "2");
     }
 
-    #[test]
-    #[ignore]
-    fn test_task1_actual() {
-        assert_eq!(format!("{}", solve_task1(ACTUAL)), "2081");
-    }
-
     #[test]
     #[ignore]
     fn test_task2() {
@@ -63,10 +86,4 @@ mod tests {
         assert_eq!(format!("{}", solve_task2("^>v<")), "3");
         assert_eq!(format!("{}", solve_task2("^v^v^v^v^v")), "11");
     }
-
-    #[test]
-    #[ignore]
-    fn test_task2_actual() {
-        assert_eq!(format!("{}", solve_task2(ACTUAL)), "2341");
-    }
 }
@@ -1,8 +1,14 @@
+use crate::{
+    parsing::ParseError,
+    registry::Registry,
+    solution::{Answer, Cancel, Params, Solution},
+};
 use md5::{Digest, Md5};
 
-pub fn solve_task1(file_content: &str) -> u32 {
+pub fn solve_task1(file_content: &str, cancel: &Cancel) -> u32 {
     (1..u32::MAX)
         .find(|x| {
+            cancel.check();
             let x = format!("{file_content}{}", x);
             let mut hasher = Md5::new();
             hasher.update(x);
@@ -14,9 +20,10 @@ pub fn solve_task1(file_content: &str) -> u32 {
         })
         .unwrap_or_default()
 }
-pub fn solve_task2(file_content: &str) -> u32 {
+pub fn solve_task2(file_content: &str, cancel: &Cancel) -> u32 {
     (1..u32::MAX)
         .find(|x| {
+            cancel.check();
             let x = format!("{file_content}{}", x);
             let mut hasher = Md5::new();
             hasher.update(x);
@@ -28,32 +35,50 @@ pub fn solve_task2(file_content: &str) -> u32 {
         })
         .unwrap_or_default()
 }
+
+pub struct Day;
+
+impl Solution for Day {
+    fn year(&self) -> u16 {
+        2015
+    }
+    fn day(&self) -> u8 {
+        4
+    }
+    fn title(&self) -> &'static str {
+        "The Ideal Stocking Stuffer"
+    }
+    fn part1(&self, file_content: &str, params: &Params) -> Result<Answer, ParseError> {
+        Ok(solve_task1(file_content, params.cancel()).into())
+    }
+    fn part2(&self, file_content: &str, params: &Params) -> Result<Answer, ParseError> {
+        Ok(solve_task2(file_content, params.cancel()).into())
+    }
+}
+
+pub fn register(registry: &mut Registry) {
+    registry.register(&Day);
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     const INPUT: &str = "abcdef";
-    const ACTUAL: &str = include_str!("../benches/y15d04.txt");
     #[test]
     #[ignore]
     fn test_task1() {
-        assert_eq!(format!("{}", solve_task1(INPUT)), "609043");
-    }
-
-    #[test]
-    #[ignore]
-    fn test_task1_actual() {
-        assert_eq!(format!("{}", solve_task1(ACTUAL)), "346386");
+        assert_eq!(
+            format!("{}", solve_task1(INPUT, &Cancel::default())),
+            "609043"
+        );
     }
 
     #[test]
     #[ignore]
     fn test_task2() {
-        assert_eq!(format!("{}", solve_task2(INPUT)), "6742839");
-    }
-
-    #[test]
-    #[ignore]
-    fn test_task2_actual() {
-        assert_eq!(format!("{}", solve_task2(ACTUAL)), "9958218");
+        assert_eq!(
+            format!("{}", solve_task2(INPUT, &Cancel::default())),
+            "6742839"
+        );
     }
 }
@@ -1,31 +1,40 @@
+use crate::{
+    input::from_str,
+    parsing::{parse_lines, ParseError},
+    registry::Registry,
+    solution::{Answer, Params, Solution},
+    trace::Trace,
+};
 use std::collections::HashSet;
 
-fn parse_nums_list(s: &str) -> impl Iterator<Item = usize> + '_ {
-    s.lines().map(|s| s.parse().unwrap())
+fn parse_nums_list(s: &str) -> Result<Vec<usize>, ParseError> {
+    parse_lines(s, from_str).collect()
 }
 
-pub fn solve_task1(file_content: &str) -> impl std::fmt::Display {
-    let nums = parse_nums_list(file_content);
+pub fn solve_task1(file_content: &str) -> Result<usize, ParseError> {
+    let nums = parse_nums_list(file_content)?;
     let mut occurred_set = HashSet::new();
     for num in nums {
         if occurred_set.contains(&num) {
             continue;
         }
         occurred_set.insert(num);
-        let other_num = 2020 - num;
+        let Some(other_num) = 2020usize.checked_sub(num) else {
+            continue;
+        };
         if occurred_set.contains(&other_num) {
-            return num * other_num;
+            return Ok(num * other_num);
         }
     }
-    return 0;
+    Ok(0)
 }
 // Right answer: 51810360
 // Iterations: 977104
-pub fn solve_task2(file_content: &str) -> impl std::fmt::Display {
-    let nums = parse_nums_list(file_content).collect::<Vec<_>>();
+pub fn solve_task2(file_content: &str, trace: &Trace) -> Result<usize, ParseError> {
+    let nums = parse_nums_list(file_content)?;
     let n = nums.len();
     let mut iter = 0;
-    for i in 0..n - 2 {
+    for i in 0..n.saturating_sub(2) {
         for j in (i + 1)..(n - 1) {
             for k in (j + 1)..n {
                 iter += 1;
@@ -33,14 +42,39 @@ pub fn solve_task2(file_content: &str) -> impl std::fmt::Display {
                 let b = nums[j];
                 let c = nums[k];
                 if a + b + c == 2020 {
-                    dbg!(iter);
-                    return a * b * c;
+                    trace.event("found", &[("iterations", &iter)]);
+                    return Ok(a * b * c);
                 }
             }
         }
     }
-    0
+    Ok(0)
+}
+
+pub struct Day;
+
+impl Solution for Day {
+    fn year(&self) -> u16 {
+        2020
+    }
+    fn day(&self) -> u8 {
+        1
+    }
+    fn title(&self) -> &'static str {
+        "Report Repair"
+    }
+    fn part1(&self, file_content: &str, _params: &Params) -> Result<Answer, ParseError> {
+        Ok(solve_task1(file_content)?.into())
+    }
+    fn part2(&self, file_content: &str, params: &Params) -> Result<Answer, ParseError> {
+        Ok(solve_task2(file_content, params.trace())?.into())
+    }
 }
+
+pub fn register(registry: &mut Registry) {
+    registry.register(&Day);
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -53,12 +87,15 @@ mod tests {
     #[test]
     #[ignore]
     fn test_task1() {
-        assert_eq!(format!("{}", solve_task1(INPUT)), "514579");
+        assert_eq!(format!("{}", solve_task1(INPUT).unwrap()), "514579");
     }
 
     #[test]
     #[ignore]
     fn test_task2() {
-        assert_eq!(format!("{}", solve_task2(INPUT)), "241861950");
+        assert_eq!(
+            format!("{}", solve_task2(INPUT, &Trace::default()).unwrap()),
+            "241861950"
+        );
     }
 }
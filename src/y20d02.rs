@@ -1,4 +1,13 @@
-use std::str::FromStr;
+use crate::{
+    parsing::{parse_lines, tag, IResult, ParseError},
+    registry::Registry,
+    solution::{Answer, Params, Solution},
+};
+use nom::{
+    character::complete::{self, anychar, not_line_ending},
+    combinator::map,
+    sequence::{preceded, separated_pair, tuple},
+};
 
 trait IsValid {
     fn is_valid(&self, password: &str) -> bool;
@@ -17,44 +26,31 @@ impl IsValid for OldWorkRule {
     }
 }
 
-impl FromStr for OldWorkRule {
-    type Err = ();
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut parts = s.split(' ');
-        let mut min_max = parts.next().unwrap().split('-');
-        let min = min_max.next().unwrap().parse().unwrap();
-        let max = min_max.next().unwrap().parse().unwrap();
-        let char = parts.next().unwrap().chars().next().unwrap();
-        Ok(OldWorkRule { char, min, max })
+impl From<Policy> for OldWorkRule {
+    fn from((min, max, char): Policy) -> Self {
+        OldWorkRule { char, min, max }
     }
 }
 
 struct CurrentRule {
     char: char,
+    /// Positions counting from 1.
     positions: [usize; 2],
 }
 
-impl FromStr for CurrentRule {
-    type Err = ();
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut parts = s.split(' ');
-        let mut positions = parts.next().unwrap().split('-');
-        let first: usize = positions.next().unwrap().parse().unwrap();
-        let second: usize = positions.next().unwrap().parse().unwrap();
-        let char = parts.next().unwrap().chars().next().unwrap();
-        Ok(Self {
+impl From<Policy> for CurrentRule {
+    fn from((first, second, char): Policy) -> Self {
+        Self {
             char,
-            positions: [usize::min(first, second) - 1, usize::max(first, second) - 1],
-        })
+            positions: [usize::min(first, second), usize::max(first, second)],
+        }
     }
 }
 
 impl IsValid for CurrentRule {
     fn is_valid(&self, password: &str) -> bool {
         let mut cnt = 0;
-        for (i, ch) in password.chars().enumerate() {
+        for (i, ch) in (1..).zip(password.chars()) {
             if i > self.positions[1] {
                 break;
             }
@@ -65,34 +61,71 @@ impl IsValid for CurrentRule {
                 cnt += 1;
             }
         }
-        return cnt == 1;
+        cnt == 1
     }
 }
 
-fn count_password<Rule>(file_content: &str) -> usize
+/// The two numbers and the letter of a password policy, e.g. `1-3 a`.
+type Policy = (usize, usize, char);
+
+fn parse_policy(input: &str) -> IResult<'_, Policy> {
+    let number = || map(complete::u32, |n| n as usize);
+    tuple((
+        number(),
+        preceded(tag("-"), number()),
+        preceded(tag(" "), anychar),
+    ))(input)
+}
+
+fn parse_entry(line: &str) -> IResult<'_, (Policy, &str)> {
+    separated_pair(parse_policy, tag(": "), not_line_ending)(line)
+}
+
+fn count_password<Rule>(file_content: &str) -> Result<usize, ParseError>
 where
-    Rule: IsValid,
-    Rule: FromStr,
-    Rule::Err: std::fmt::Debug,
+    Rule: IsValid + From<Policy>,
 {
     let mut res = 0;
-    for line in file_content.lines() {
-        let mut parts = line.split(": ");
-        let rule = parts.next().unwrap().parse::<Rule>().unwrap();
-        let password = parts.next().unwrap();
-        if rule.is_valid(password) {
+    for entry in parse_lines(file_content, parse_entry) {
+        let (policy, password) = entry?;
+        if Rule::from(policy).is_valid(password) {
             res += 1;
         }
     }
-    res
+    Ok(res)
 }
 
-pub fn solve_task1(file_content: &str) -> impl std::fmt::Display {
+pub fn solve_task1(file_content: &str) -> Result<usize, ParseError> {
     count_password::<OldWorkRule>(file_content)
 }
-pub fn solve_task2(file_content: &str) -> impl std::fmt::Display {
+pub fn solve_task2(file_content: &str) -> Result<usize, ParseError> {
     count_password::<CurrentRule>(file_content)
 }
+
+pub struct Day;
+
+impl Solution for Day {
+    fn year(&self) -> u16 {
+        2020
+    }
+    fn day(&self) -> u8 {
+        2
+    }
+    fn title(&self) -> &'static str {
+        "Password Philosophy"
+    }
+    fn part1(&self, file_content: &str, _params: &Params) -> Result<Answer, ParseError> {
+        Ok(solve_task1(file_content)?.into())
+    }
+    fn part2(&self, file_content: &str, _params: &Params) -> Result<Answer, ParseError> {
+        Ok(solve_task2(file_content)?.into())
+    }
+}
+
+pub fn register(registry: &mut Registry) {
+    registry.register(&Day);
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -102,11 +135,25 @@ mod tests {
     #[ignore]
     #[test]
     fn test_task1() {
-        assert_eq!(format!("{}", solve_task1(INPUT)), "2");
+        assert_eq!(format!("{}", solve_task1(INPUT).unwrap()), "2");
     }
     #[ignore]
     #[test]
     fn test_task2() {
-        assert_eq!(format!("{}", solve_task2(INPUT)), "1");
+        assert_eq!(format!("{}", solve_task2(INPUT).unwrap()), "1");
+    }
+
+    #[test]
+    fn test_invalid_entry() {
+        assert_eq!(
+            solve_task1("1-3 a: abcde\n1 b: cdefg")
+                .unwrap_err()
+                .to_string(),
+            "line 2, column 2: expected \"-\", found \" b: cdefg\""
+        );
+        assert_eq!(
+            solve_task2("1-3 a abcde").unwrap_err().to_string(),
+            "line 1, column 6: expected \": \", found \" abcde\""
+        );
     }
 }
@@ -1,96 +1,79 @@
-use std::str::FromStr;
+use crate::{
+    interval::IntervalSet,
+    parsing::{parse_lines, tag, IResult, ParseError},
+    registry::Registry,
+    solution::{Answer, Params, Solution},
+};
+use itertools::process_results;
+use nom::character::complete;
 
-use nom::IResult;
-
-fn parse_next<'a, T, U>(x: &mut T) -> U
-where
-    T: Iterator<Item = &'a str>,
-    U: FromStr,
-    U::Err: std::fmt::Debug,
-{
-    x.next().unwrap().parse().unwrap()
-}
-
-struct Range {
-    start: u32,
-    end: u32,
-}
-
-impl Range {
-    #[inline]
-    fn fully_contains(&self, other: &Range) -> bool {
-        self.start <= other.start && self.end >= other.end
-    }
-
-    #[inline]
-    fn overlaps(&self, other: &Range) -> bool {
-        self.start <= other.end && self.end >= other.start
-    }
-}
-
-impl FromStr for Range {
-    type Err = ();
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut iter = s.split('-');
-        Ok(Range {
-            start: parse_next(&mut iter),
-            end: parse_next(&mut iter),
-        })
-    }
-}
-
-struct Pair(Range, Range);
+struct Pair(IntervalSet<u32>, IntervalSet<u32>);
 
 impl Pair {
     #[inline]
     fn one_contains_other(&self) -> bool {
-        self.0.fully_contains(&self.1) || self.1.fully_contains(&self.0)
+        self.0.is_superset(&self.1) || self.1.is_superset(&self.0)
     }
 
     #[inline]
     fn has_overlaps(&self) -> bool {
-        self.0.overlaps(&self.1)
+        !self.0.is_disjoint(&self.1)
     }
 }
 
-impl FromStr for Pair {
-    type Err = ();
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut iter = s.split(',');
-        Ok(Self(parse_next(&mut iter), parse_next(&mut iter)))
-    }
-}
-
-fn parse_range(line: &str) -> IResult<&str, Range> {
-    let (input, start) = nom::character::complete::u32(line)?;
-    let (input, _) = nom::bytes::complete::tag("-")(input)?;
-    let (input, end) = nom::character::complete::u32(input)?;
-    return Ok((input, Range { start, end }));
+fn parse_range(line: &str) -> IResult<'_, IntervalSet<u32>> {
+    let (input, start) = complete::u32(line)?;
+    let (input, _) = tag("-")(input)?;
+    let (input, end) = complete::u32(input)?;
+    Ok((input, IntervalSet::from(start..=end)))
 }
 
-fn parse_pair(line: &str) -> IResult<&str, Pair> {
+fn parse_pair(line: &str) -> IResult<'_, Pair> {
     let (input, first) = parse_range(line)?;
-    let (input, _) = nom::bytes::complete::tag(",")(input)?;
+    let (input, _) = tag(",")(input)?;
     let (input, second) = parse_range(input)?;
 
     Ok((input, Pair(first, second)))
 }
 
-fn solve(file_content: &str, predicate: impl Fn(&Pair) -> bool) -> usize {
-    file_content
-        .lines()
-        .map(|line| parse_pair(line).unwrap().1)
-        .filter(predicate)
-        .count()
+fn solve(file_content: &str, predicate: impl Fn(&Pair) -> bool) -> Result<usize, ParseError> {
+    process_results(parse_lines(file_content, parse_pair), |pairs| {
+        pairs.filter(predicate).count()
+    })
 }
 
-pub fn solve_task1(file_content: &str) -> usize {
+pub fn solve_task1(file_content: &str) -> Result<usize, ParseError> {
     solve(file_content, Pair::one_contains_other)
 }
 
-pub fn solve_task2(file_content: &str) -> usize {
+pub fn solve_task2(file_content: &str) -> Result<usize, ParseError> {
     solve(file_content, Pair::has_overlaps)
 }
+
+pub struct Day;
+
+impl Solution for Day {
+    fn year(&self) -> u16 {
+        2022
+    }
+    fn day(&self) -> u8 {
+        4
+    }
+    fn title(&self) -> &'static str {
+        "Camp Cleanup"
+    }
+    fn part1(&self, file_content: &str, _params: &Params) -> Result<Answer, ParseError> {
+        Ok(solve_task1(file_content)?.into())
+    }
+    fn part2(&self, file_content: &str, _params: &Params) -> Result<Answer, ParseError> {
+        Ok(solve_task2(file_content)?.into())
+    }
+}
+
+pub fn register(registry: &mut Registry) {
+    registry.register(&Day);
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -104,12 +87,12 @@ mod tests {
     #[test]
     #[ignore]
     fn test_task1() {
-        assert_eq!(format!("{}", solve_task1(INPUT)), "2");
+        assert_eq!(format!("{}", solve_task1(INPUT).unwrap()), "2");
     }
 
     #[test]
     #[ignore]
     fn test_task2() {
-        assert_eq!(format!("{}", solve_task2(INPUT)), "4");
+        assert_eq!(format!("{}", solve_task2(INPUT).unwrap()), "4");
     }
 }
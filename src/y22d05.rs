@@ -1,6 +1,14 @@
-use nom::IResult;
+use crate::{
+    input::{blocks, numbers, Normalization},
+    parsing::{parse_all, tag, Error, Expected, IResult, ParseError},
+    registry::Registry,
+    solution::{Answer, Params, Solution},
+};
+use nom::{character::complete, combinator::map_opt, error::context};
 
 type Board = Vec<Vec<char>>;
+/// The moves with their lines to report the moves that cannot be made.
+type Moves<'a> = Vec<(&'a str, Move)>;
 
 #[derive(Debug)]
 struct Move {
@@ -9,27 +17,17 @@ struct Move {
     to: usize,
 }
 
-fn split_by_empty_line(input: &str) -> (Vec<&str>, Vec<&str>) {
-    let mut first = Vec::new();
-    let mut second = Vec::new();
-    let mut reading_first = true;
-    for line in input.lines() {
-        if line.is_empty() {
-            reading_first = false;
-            continue;
-        }
-        if reading_first {
-            first.push(line)
-        } else {
-            second.push(line)
-        }
-    }
-    return (first, second);
-}
-
-fn parse_map(map_lines: Vec<&str>) -> Vec<Vec<char>> {
-    let stacks_amount = (map_lines[0].chars().count() + 2) >> 2;
+/// Parses the crate diagram, the columns missing at the ends of the lines are empty.
+fn parse_map(input: &str, map: &str) -> Result<Board, ParseError> {
+    let map_lines = map.lines().collect::<Vec<_>>();
     let highest_stack = map_lines.len() - 1;
+    let stacks_amount = numbers::<usize>(map_lines[highest_stack]).count();
+    if stacks_amount == 0 {
+        return Err(ParseError::new(
+            input,
+            Error::new(map_lines[highest_stack], Expected::Context("stack numbers")),
+        ));
+    }
     let mut stacks = Vec::new();
     let chars: Vec<Vec<char>> = map_lines
         .into_iter()
@@ -42,7 +40,10 @@ fn parse_map(map_lines: Vec<&str>) -> Vec<Vec<char>> {
             if item >= highest_stack {
                 break;
             }
-            let char = chars[highest_stack - item - 1][stack_index * 4 + 1];
+            let char = chars[highest_stack - item - 1]
+                .get(stack_index * 4 + 1)
+                .copied()
+                .unwrap_or(' ');
             if char.is_whitespace() {
                 break;
             }
@@ -52,64 +53,96 @@ fn parse_map(map_lines: Vec<&str>) -> Vec<Vec<char>> {
 
         stacks.push(stack_list);
     }
-    stacks
+    Ok(stacks)
 }
 
-fn parse_move(line: &str) -> IResult<&str, Move> {
-    let (input, _) = nom::bytes::complete::tag("move ")(line)?;
-    let (input, amount) = nom::character::complete::u32(input)?;
-    let (input, _) = nom::bytes::complete::tag(" from ")(input)?;
-    let (input, from) = nom::character::complete::u32(input)?;
-    let (input, _) = nom::bytes::complete::tag(" to ")(input)?;
-    let (input, to) = nom::character::complete::u32(input)?;
+/// Parses the number of one of the `stacks` stacks into its index.
+fn parse_stack<'a>(stacks: usize) -> impl FnMut(&'a str) -> IResult<'a, usize> {
+    let stack = map_opt(complete::u32, move |number| {
+        (1..=stacks)
+            .contains(&(number as usize))
+            .then(|| number as usize - 1)
+    });
+    context("stack number", stack)
+}
 
-    return Ok((
-        input,
-        Move {
-            amount,
-            from: from as usize - 1,
-            to: to as usize - 1,
-        },
-    ));
+fn parse_move(stacks: usize) -> impl FnMut(&str) -> IResult<'_, Move> {
+    move |line| {
+        let (input, _) = tag("move ")(line)?;
+        let (input, amount) = complete::u32(input)?;
+        let (input, _) = tag(" from ")(input)?;
+        let (input, from) = parse_stack(stacks)(input)?;
+        let (input, _) = tag(" to ")(input)?;
+        let (input, to) = parse_stack(stacks)(input)?;
+
+        Ok((input, Move { amount, from, to }))
+    }
 }
 
-fn parse_moves(moves_lines: Vec<&str>) -> impl Iterator<Item = Move> + '_ {
-    moves_lines
-        .into_iter()
-        .map(|line| parse_move(line).unwrap().1)
+fn parse_moves<'a>(input: &'a str, moves: &'a str, stacks: usize) -> Result<Moves<'a>, ParseError> {
+    moves
+        .lines()
+        .map(|line| Ok((line, parse_all(input, line, parse_move(stacks))?)))
+        .collect()
 }
 
-fn parse_input(input: &str) -> (Board, impl Iterator<Item = Move> + '_) {
-    let (map_lines, moves_lines) = split_by_empty_line(input);
-    (parse_map(map_lines), parse_moves(moves_lines))
+fn parse_input(input: &str) -> Result<(Board, Moves<'_>), ParseError> {
+    let end = &input[input.len()..];
+    let missing = |block| ParseError::new(input, Error::new(end, Expected::Context(block)));
+    let mut blocks = blocks(input);
+    let map = parse_map(
+        input,
+        blocks.next().ok_or_else(|| missing("crate diagram"))?,
+    )?;
+    let moves = parse_moves(
+        input,
+        blocks.next().ok_or_else(|| missing("moves"))?,
+        map.len(),
+    )?;
+    Ok((map, moves))
 }
 
-fn apply_move_one_by_one(m: Move, stacks: &mut Vec<Vec<char>>) {
+fn apply_move_one_by_one(m: Move, stacks: &mut [Vec<char>]) {
     for _ in 0..m.amount {
         let item = stacks[m.from].pop().unwrap();
         stacks[m.to].push(item);
     }
 }
 
-fn solve(file_content: &str, apply_move: impl Fn(Move, &mut Vec<Vec<char>>)) -> String {
-    let (mut stacks, moves) = parse_input(file_content);
+fn solve(
+    file_content: &str,
+    apply_move: impl Fn(Move, &mut [Vec<char>]),
+) -> Result<String, ParseError> {
+    let (mut stacks, moves) = parse_input(file_content)?;
     {
-        for m in moves {
+        for (line, m) in moves {
+            if stacks[m.from].len() < m.amount as usize {
+                return Err(ParseError::new(
+                    file_content,
+                    Error::new(line, Expected::Context("move of the crates on the stack")),
+                ));
+            }
             apply_move(m, &mut stacks)
         }
     }
     let result = stacks
         .into_iter()
-        .map(|stack| *stack.last().unwrap())
-        .collect::<String>();
-    result
+        .map(|stack| stack.last().copied())
+        .collect::<Option<String>>();
+    result.ok_or_else(|| {
+        let end = &file_content[file_content.len()..];
+        ParseError::new(
+            file_content,
+            Error::new(end, Expected::Context("crate on top of every stack")),
+        )
+    })
 }
 
-pub fn solve_task1(file_content: &str) -> String {
+pub fn solve_task1(file_content: &str) -> Result<String, ParseError> {
     solve(file_content, apply_move_one_by_one)
 }
 
-fn apply_move_with_multiple_at_once(m: Move, stacks: &mut Vec<Vec<char>>) {
+fn apply_move_with_multiple_at_once(m: Move, stacks: &mut [Vec<char>]) {
     if m.from == m.to {
         return;
     }
@@ -122,9 +155,38 @@ fn apply_move_with_multiple_at_once(m: Move, stacks: &mut Vec<Vec<char>>) {
     stacks[m.to].extend(items);
 }
 
-pub fn solve_task2(file_content: &str) -> impl std::fmt::Display {
+pub fn solve_task2(file_content: &str) -> Result<String, ParseError> {
     solve(file_content, apply_move_with_multiple_at_once)
 }
+
+pub struct Day;
+
+impl Solution for Day {
+    fn year(&self) -> u16 {
+        2022
+    }
+    fn day(&self) -> u8 {
+        5
+    }
+    fn title(&self) -> &'static str {
+        "Supply Stacks"
+    }
+    fn part1(&self, file_content: &str, _params: &Params) -> Result<Answer, ParseError> {
+        Ok(solve_task1(file_content)?.into())
+    }
+    fn part2(&self, file_content: &str, _params: &Params) -> Result<Answer, ParseError> {
+        Ok(solve_task2(file_content)?.into())
+    }
+    fn normalization(&self) -> Normalization {
+        // the crate diagram is indexed by columns, so its trailing spaces are kept
+        Normalization { trim_lines: false }
+    }
+}
+
+pub fn register(registry: &mut Registry) {
+    registry.register(&Day);
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -140,11 +202,48 @@ move 1 from 1 to 2";
     #[test]
     #[ignore]
     fn test_task1() {
-        assert_eq!(format!("{}", solve_task1(INPUT)), "CMZ");
+        assert_eq!(format!("{}", solve_task1(INPUT).unwrap()), "CMZ");
     }
     #[test]
     #[ignore]
     fn test_task2() {
-        assert_eq!(format!("{}", solve_task2(INPUT)), "MCD");
+        assert_eq!(format!("{}", solve_task2(INPUT).unwrap()), "MCD");
+    }
+    #[test]
+    fn test_invalid_input() {
+        let error = |input| solve_task1(input).unwrap_err().to_string();
+        assert_eq!(
+            error(""),
+            "line 1, column 1: expected crate diagram, found end of line"
+        );
+        assert_eq!(
+            error("[A]\n 1 \n"),
+            "line 3, column 1: expected moves, found end of line"
+        );
+        assert_eq!(
+            error("[A]\n 1 \n\nmove 1 from 0 to 1"),
+            "line 4, column 13: expected stack number, found \"0 to 1\""
+        );
+        assert_eq!(
+            error("[A]\n 1 \n\nmove 1 from 1 to 2"),
+            "line 4, column 18: expected stack number, found \"2\""
+        );
+        assert_eq!(
+            error("[A]\n 1 \n\nmove 2 from 1 to 1"),
+            "line 4, column 1: expected move of the crates on the stack, found \"move 2 from 1 to 1\""
+        );
+        assert_eq!(
+            error("    [A]\n 1   2\n\nmove 1 from 2 to 2"),
+            "line 4, column 19: expected crate on top of every stack, found end of line"
+        );
+    }
+    #[test]
+    fn test_trimmed_diagram() {
+        let input = INPUT
+            .lines()
+            .map(str::trim_end)
+            .collect::<Vec<_>>()
+            .join("\n");
+        assert_eq!(solve_task1(&input).unwrap(), "CMZ");
     }
 }
@@ -1,12 +1,22 @@
+use crate::{
+    parsing::ParseError,
+    registry::Registry,
+    solution::{Answer, Params, Solution},
+};
+
 fn solve<const N: usize>(file_content: &str) -> usize {
     // since the input is always ASCII characters - we use assumption that each character is written as single byte
     /* Invariant 1: cnt contains the count of each character inside the sequence of N chars we look at the moment  */
     /* Invariant 2: dublicates contains the number of dublicates in the current sequence of N chars */
     /* Invariant 3: current sequence has N last characters of the input */
     let chars = file_content.as_bytes();
-    let mut cnt = [0 as usize; 256];
+    let mut cnt = [0_usize; 256];
     let mut dublicates = 0;
-    for &c in &chars[..N] {
+    // the input shorter than the marker has no marker either
+    let Some(window) = chars.get(..N) else {
+        return 0;
+    };
+    for &c in window {
         cnt[c as usize] += 1;
         if cnt[c as usize] == 2 {
             dublicates += 1;
@@ -41,9 +51,34 @@ fn solve<const N: usize>(file_content: &str) -> usize {
 pub fn solve_task1(file_content: &str) -> usize {
     solve::<4>(file_content)
 }
-pub fn solve_task2(file_content: &str) -> impl std::fmt::Display {
+pub fn solve_task2(file_content: &str) -> usize {
     solve::<14>(file_content)
 }
+
+pub struct Day;
+
+impl Solution for Day {
+    fn year(&self) -> u16 {
+        2022
+    }
+    fn day(&self) -> u8 {
+        6
+    }
+    fn title(&self) -> &'static str {
+        "Tuning Trouble"
+    }
+    fn part1(&self, file_content: &str, _params: &Params) -> Result<Answer, ParseError> {
+        Ok(solve_task1(file_content).into())
+    }
+    fn part2(&self, file_content: &str, _params: &Params) -> Result<Answer, ParseError> {
+        Ok(solve_task2(file_content).into())
+    }
+}
+
+pub fn register(registry: &mut Registry) {
+    registry.register(&Day);
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -94,4 +129,11 @@ mod tests {
             "26"
         );
     }
+
+    #[test]
+    fn test_short_input() {
+        assert_eq!(solve_task1(""), 0);
+        assert_eq!(solve_task1("1"), 0);
+        assert_eq!(solve_task2("abcd"), 0);
+    }
 }
@@ -2,15 +2,21 @@ mod io;
 mod parse;
 mod tree;
 
+use crate::{
+    parsing::ParseError,
+    registry::Registry,
+    solution::{Answer, Parameter, Params, Solution},
+};
+
 use self::{parse::parse_io, tree::Tree};
 
-fn file_content_to_tree(file_content: &str) -> Tree {
-    parse_io(file_content).collect()
+fn file_content_to_tree(file_content: &str) -> Result<Tree<'_>, ParseError> {
+    Ok(parse_io(file_content)?.into_iter().collect())
 }
 
 // 1581595
-pub fn solve_task1(file_content: &str) -> usize {
-    file_content_to_tree(file_content)
+pub fn solve_task1(file_content: &str) -> Result<usize, ParseError> {
+    Ok(file_content_to_tree(file_content)?
         .into_iter()
         .filter_map(|x| {
             if !x.is_dir() {
@@ -22,19 +28,16 @@ pub fn solve_task1(file_content: &str) -> usize {
             }
             Some(size)
         })
-        .sum()
+        .sum())
 }
 
 // 1544176
-pub fn solve_task2(file_content: &str) -> usize {
-    let tree = file_content_to_tree(file_content);
-
-    const TOTAL: usize = 70000000;
-    const REQUIRED: usize = 30000000;
+pub fn solve_task2(file_content: &str, total: usize, required: usize) -> Result<usize, ParseError> {
+    let tree = file_content_to_tree(file_content)?;
 
-    let currently_free = TOTAL - tree.total_size();
+    let currently_free = total.saturating_sub(tree.total_size());
 
-    let to_delete = REQUIRED - currently_free;
+    let to_delete = required.saturating_sub(currently_free);
 
     let mut possible: Vec<usize> = tree
         .into_iter()
@@ -51,8 +54,43 @@ pub fn solve_task2(file_content: &str) -> usize {
         .collect();
 
     possible.sort_by(|a, b| b.cmp(a));
-    possible.pop().unwrap_or_default()
+    Ok(possible.pop().unwrap_or_default())
+}
+
+const PARAMETERS: &[Parameter] = &[
+    Parameter::new::<usize>("total", "70000000"),
+    Parameter::new::<usize>("required", "30000000"),
+];
+
+pub struct Day;
+
+impl Solution for Day {
+    fn year(&self) -> u16 {
+        2022
+    }
+    fn day(&self) -> u8 {
+        7
+    }
+    fn title(&self) -> &'static str {
+        "No Space Left On Device"
+    }
+    fn part1(&self, file_content: &str, _params: &Params) -> Result<Answer, ParseError> {
+        Ok(solve_task1(file_content)?.into())
+    }
+    fn part2(&self, file_content: &str, params: &Params) -> Result<Answer, ParseError> {
+        let total = params.get("total");
+        let required = params.get("required");
+        Ok(solve_task2(file_content, total, required)?.into())
+    }
+    fn parameters(&self) -> &'static [Parameter] {
+        PARAMETERS
+    }
+}
+
+pub fn register(registry: &mut Registry) {
+    registry.register(&Day);
 }
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -82,11 +120,28 @@ $ ls
     #[test]
     #[ignore]
     fn test_task1() {
-        assert_eq!(format!("{}", solve_task1(INPUT)), "95437");
+        assert_eq!(format!("{}", solve_task1(INPUT).unwrap()), "95437");
     }
     #[test]
     #[ignore]
     fn test_task2() {
-        assert_eq!(format!("{}", solve_task2(INPUT)), "24933642");
+        assert_eq!(
+            format!("{}", solve_task2(INPUT, 70000000, 30000000).unwrap()),
+            "24933642"
+        );
+    }
+
+    #[test]
+    fn test_invalid_output() {
+        assert_eq!(
+            solve_task1("$ cd /\n$ ls\ndir a\n14848514\n$ cd a")
+                .unwrap_err()
+                .to_string(),
+            "line 4, column 9: expected space, found end of line"
+        );
+        assert_eq!(
+            solve_task1("$ cd /\n$ rm a").unwrap_err().to_string(),
+            "line 2, column 3: expected \"cd \" or \"ls\", found \"rm a\""
+        );
     }
 }
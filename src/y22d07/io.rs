@@ -6,7 +6,7 @@ pub struct File<'input> {
 
 impl File<'_> {
     pub fn has_name(&self, name: &str) -> bool {
-        return self.name == name;
+        self.name == name
     }
 }
 
@@ -1,12 +1,11 @@
-use std::iter::Peekable;
-
 use nom::{
-    branch, bytes,
+    branch,
     character::{self, complete::space1},
-    sequence, IResult,
+    sequence,
 };
 
 use super::io::{ChangeDirArgument, File, Node, IO};
+use crate::parsing::{parse_all, tag, IResult, ParseError};
 
 #[derive(Debug, PartialEq, Eq)]
 enum Query<'input> {
@@ -14,79 +13,51 @@ enum Query<'input> {
     ChangeDir(ChangeDirArgument<'input>),
 }
 
-struct IOParser<'input, Lines>
-where
-    Lines: Iterator<Item = &'input str>,
-{
-    lines: Peekable<Lines>,
-}
-
-impl<'input, Lines> Iterator for IOParser<'input, Lines>
-where
-    Lines: Iterator<Item = &'input str>,
-{
-    type Item = IO<'input>;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        match self.lines.next() {
-            None => None,
-            Some(line) => {
-                let (_, query) = parse_query(line).expect("valid query is expected");
-                match query {
-                    Query::List => {
-                        let mut res = Vec::new();
-                        loop {
-                            match self.lines.peek() {
-                                None => return Some(IO::List(res)),
-                                Some(line) => match parse_node(line) {
-                                    Ok((_, r)) => {
-                                        self.lines.next().unwrap();
-                                        res.push(r);
-                                    }
-                                    Err(_) => return Some(IO::List(res)),
-                                },
-                            }
-                        }
-                    }
-                    Query::ChangeDir(argument) => Some(IO::ChangeDir(argument)),
+/// Parses the queries together with their output, which lasts until the next query.
+pub fn parse_io(file_content: &str) -> Result<Vec<IO<'_>>, ParseError> {
+    let mut lines = file_content.lines().peekable();
+    let mut res = Vec::new();
+    while let Some(line) = lines.next() {
+        match parse_all(file_content, line, parse_query)? {
+            Query::List => {
+                let mut nodes = Vec::new();
+                while let Some(line) = lines.next_if(|line| !line.starts_with('$')) {
+                    nodes.push(parse_all(file_content, line, parse_node)?);
                 }
+                res.push(IO::List(nodes));
             }
+            Query::ChangeDir(argument) => res.push(IO::ChangeDir(argument)),
         }
     }
+    Ok(res)
 }
 
-pub fn parse_io(file_content: &str) -> impl Iterator<Item = IO<'_>> {
-    IOParser {
-        lines: file_content.lines().peekable(),
-    }
-}
-
-fn parse_change_dir_query(line: &str) -> IResult<&str, Query> {
+fn parse_change_dir_query(line: &str) -> IResult<'_, Query<'_>> {
     nom::combinator::map(
         sequence::preceded(
-            bytes::complete::tag("cd "),
+            tag("cd "),
             nom::branch::alt((
                 nom::combinator::map(character::complete::alpha1, |name| {
                     ChangeDirArgument::Directory(name)
                 }),
-                nom::combinator::map(bytes::complete::tag(".."), |_| ChangeDirArgument::Parent),
-                nom::combinator::map(bytes::complete::tag("/"), |_| ChangeDirArgument::Root),
+                nom::combinator::map(tag(".."), |_| ChangeDirArgument::Parent),
+                nom::combinator::map(tag("/"), |_| ChangeDirArgument::Root),
             )),
         ),
-        |arg| Query::ChangeDir(arg),
+        Query::ChangeDir,
     )(line)
 }
-fn parse_list_query(line: &str) -> IResult<&str, Query> {
-    nom::combinator::map(bytes::complete::tag("ls"), |_| Query::List)(line)
+fn parse_list_query(line: &str) -> IResult<'_, Query<'_>> {
+    nom::combinator::map(tag("ls"), |_| Query::List)(line)
 }
-fn parse_query(line: &str) -> IResult<&str, Query> {
+fn parse_query(line: &str) -> IResult<'_, Query<'_>> {
     sequence::preceded(
-        bytes::complete::tag("$ "),
+        tag("$ "),
         branch::alt((parse_change_dir_query, parse_list_query)),
     )(line)
 }
 
-fn parse_file(line: &str) -> IResult<&str, Node> {
+fn parse_file(line: &str) -> IResult<'_, Node<'_>> {
     nom::combinator::map(
         nom::sequence::separated_pair(
             nom::character::complete::u32,
@@ -101,17 +72,14 @@ fn parse_file(line: &str) -> IResult<&str, Node> {
         },
     )(line)
 }
-fn parse_directory(line: &str) -> IResult<&str, Node> {
+fn parse_directory(line: &str) -> IResult<'_, Node<'_>> {
     nom::combinator::map(
-        nom::sequence::preceded(
-            nom::bytes::complete::tag("dir "),
-            nom::character::complete::not_line_ending,
-        ),
-        |c| Node::Directory(c),
+        nom::sequence::preceded(tag("dir "), nom::character::complete::not_line_ending),
+        Node::Directory,
     )(line)
 }
 
-fn parse_node(line: &str) -> IResult<&str, Node> {
+fn parse_node(line: &str) -> IResult<'_, Node<'_>> {
     nom::branch::alt((parse_file, parse_directory))(line)
 }
 
@@ -1,87 +1,71 @@
-fn parse_grid(file_content: &str) -> Vec<Vec<u8>> {
-    file_content
-        .lines()
-        .map(|line| line.chars().map(|c| c as u8 - b'0').collect())
-        .collect()
-}
-
-fn nullify(arr: &[Vec<u8>]) -> Vec<Vec<u8>> {
-    arr.iter().map(|line| vec![0; line.len()]).collect()
+use crate::{
+    grid::Grid,
+    parsing::ParseError,
+    registry::Registry,
+    solution::{Answer, Params, Solution},
+};
+
+fn parse_grid(file_content: &str) -> Result<Grid<u8>, ParseError> {
+    Grid::parse(file_content, |_, c| c.to_digit(10).map(|d| d as u8))
 }
 
 // 1705
-pub fn solve_task1(file_content: &str) -> usize {
-    let grid = parse_grid(file_content);
+pub fn solve_task1(file_content: &str) -> Result<usize, ParseError> {
+    let grid = parse_grid(file_content)?;
 
-    let rows = grid.len();
-    let cols = grid[0].len();
+    let rows = grid.rows();
+    let cols = grid.cols();
+    if rows == 0 || cols == 0 {
+        return Ok(0);
+    }
 
-    let mut top: Vec<Vec<u8>> = nullify(&grid);
-    let mut right: Vec<Vec<u8>> = nullify(&grid);
-    let mut bottom: Vec<Vec<u8>> = nullify(&grid);
-    let mut left: Vec<Vec<u8>> = nullify(&grid);
+    let mut top = Grid::new(rows, cols, 0);
+    let mut right = Grid::new(rows, cols, 0);
+    let mut bottom = Grid::new(rows, cols, 0);
+    let mut left = Grid::new(rows, cols, 0);
 
     for i in (0..(rows - 1)).rev() {
         for j in 0..cols {
-            top[i][j] = top[i + 1][j].max(grid[i + 1][j]);
+            top[(i, j)] = top[(i + 1, j)].max(grid[(i + 1, j)]);
         }
     }
 
     for i in 1..rows {
         for j in 0..cols {
-            bottom[i][j] = bottom[i - 1][j].max(grid[i - 1][j]);
+            bottom[(i, j)] = bottom[(i - 1, j)].max(grid[(i - 1, j)]);
         }
     }
 
     for j in (0..(cols - 1)).rev() {
         for i in 0..rows {
-            right[i][j] = right[i][j + 1].max(grid[i][j + 1]);
+            right[(i, j)] = right[(i, j + 1)].max(grid[(i, j + 1)]);
         }
     }
     for j in 1..cols {
         for i in 0..rows {
-            left[i][j] = left[i][j - 1].max(grid[i][j - 1]);
+            left[(i, j)] = left[(i, j - 1)].max(grid[(i, j - 1)]);
         }
     }
 
     let mut res = 0;
-    for (row, line) in grid.iter().enumerate() {
-        for col in 0..line.len() {
-            if row == 0 || col == 0 || row == rows - 1 || col == cols - 1 {
+    for ((row, col), &v) in grid.iter() {
+        if row == 0 || col == 0 || row == rows - 1 || col == cols - 1 {
+            res += 1;
+            continue;
+        }
+        let pos = (row, col);
+        if top[pos] == 0 || right[pos] == 0 || bottom[pos] == 0 || left[pos] == 0 {
+            if v > 0 {
                 res += 1;
-                continue;
-            }
-            let v = grid[row][col];
-            if top[row][col] == 0
-                || right[row][col] == 0
-                || bottom[row][col] == 0
-                || left[row][col] == 0
-            {
-                if v > 0 {
-                    res += 1;
-                }
-                continue;
             }
+            continue;
+        }
 
-            if top[row][col] < v {
-                res += 1;
-                continue;
-            }
-            if right[row][col] < v {
-                res += 1;
-                continue;
-            }
-            if bottom[row][col] < v {
-                res += 1;
-                continue;
-            }
-            if left[row][col] < v {
-                res += 1;
-                continue;
-            }
+        if top[pos] < v || right[pos] < v || bottom[pos] < v || left[pos] < v {
+            res += 1;
         }
     }
-    res
+    Ok(res)
 }
 
 struct TakeWhileInclusiveIter<T, P>
@@ -126,52 +110,61 @@ where
                     return Some(v);
                 }
                 self.finished = true;
-                return Some(v);
+                Some(v)
             }
             None => None,
         }
     }
 }
 
-fn get_score(grid: &Vec<Vec<u8>>, row: usize, col: usize) -> usize {
-    let cols = grid[0].len();
-    let top = (0..row)
-        .rev()
-        .map(|ind| grid[ind][col])
-        .take_while_inclusive(|v| *v < grid[row][col])
-        .count();
-    let right = (col + 1..cols)
-        .map(|ind| grid[row][ind])
-        .take_while_inclusive(|v| *v < grid[row][col])
-        .count();
-    let bottom = ((row + 1)..grid.len())
-        .map(|ind| grid[ind][col])
-        .take_while_inclusive(|v| *v < grid[row][col])
-        .count();
-    let left = (0..col)
-        .rev()
-        .map(|ind| grid[row][ind])
-        .take_while_inclusive(|v| *v < grid[row][col])
-        .count();
+fn count_visible<'a>(trees: impl Iterator<Item = &'a u8>, height: u8) -> usize {
+    trees.take_while_inclusive(|v| **v < height).count()
+}
+
+fn get_score(grid: &Grid<u8>, row: usize, col: usize) -> usize {
+    let height = grid[(row, col)];
+    let top = count_visible(grid.column(col).take(row).rev(), height);
+    let right = count_visible(grid.row(row)[col + 1..].iter(), height);
+    let bottom = count_visible(grid.column(col).skip(row + 1), height);
+    let left = count_visible(grid.row(row)[..col].iter().rev(), height);
     top * right * bottom * left
 }
 // 371200
-pub fn solve_task2(file_content: &str) -> usize {
-    let grid = parse_grid(file_content);
-    let mut res = 0;
-    for (row, line) in grid.iter().enumerate() {
-        for col in 0..line.len() {
-            let score = get_score(&grid, row, col);
-            res = res.max(score);
-        }
+pub fn solve_task2(file_content: &str) -> Result<usize, ParseError> {
+    let grid = parse_grid(file_content)?;
+    Ok(grid
+        .positions()
+        .map(|(row, col)| get_score(&grid, row, col))
+        .max()
+        .unwrap_or_default())
+}
+
+pub struct Day;
+
+impl Solution for Day {
+    fn year(&self) -> u16 {
+        2022
+    }
+    fn day(&self) -> u8 {
+        8
+    }
+    fn title(&self) -> &'static str {
+        "Treetop Tree House"
+    }
+    fn part1(&self, file_content: &str, _params: &Params) -> Result<Answer, ParseError> {
+        Ok(solve_task1(file_content)?.into())
+    }
+    fn part2(&self, file_content: &str, _params: &Params) -> Result<Answer, ParseError> {
+        Ok(solve_task2(file_content)?.into())
     }
-    res
+}
+
+pub fn register(registry: &mut Registry) {
+    registry.register(&Day);
 }
 
 #[cfg(test)]
 mod tests {
-    use std::fs;
-
     use super::*;
     const INPUT: &str = "30373
 25512
@@ -179,37 +172,34 @@ mod tests {
 33549
 35390";
 
-    #[ignore]
-    #[test]
-    fn test_actual() {
-        let str = fs::read_to_string("./benches/y22d8.txt").unwrap_or_default();
-        let res = solve_task1(&str);
-        assert_eq!(res, 1705);
-    }
-
     #[ignore]
     #[test]
     fn test_task1() {
-        assert_eq!(format!("{}", solve_task1(INPUT.trim())), "21");
+        assert_eq!(format!("{}", solve_task1(INPUT.trim()).unwrap()), "21");
     }
 
     #[ignore]
     #[test]
     fn test_score_1() {
-        let grid = parse_grid(INPUT);
+        let grid = parse_grid(INPUT).unwrap();
         let score = get_score(&grid, 1, 2);
         assert_eq!(score, 4)
     }
     #[ignore]
     #[test]
     fn test_score_2() {
-        let grid = parse_grid(INPUT);
+        let grid = parse_grid(INPUT).unwrap();
         let score = get_score(&grid, 3, 2);
         assert_eq!(score, 8)
     }
     #[ignore]
     #[test]
     fn test_task2() {
-        assert_eq!(format!("{}", solve_task2(INPUT)), "8");
+        assert_eq!(format!("{}", solve_task2(INPUT).unwrap()), "8");
+    }
+    #[test]
+    fn test_empty_grid() {
+        assert_eq!(solve_task1("").unwrap(), 0);
+        assert_eq!(solve_task2("").unwrap(), 0);
     }
 }
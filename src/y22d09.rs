@@ -1,43 +1,158 @@
-use std::collections::HashSet;
+use crate::{
+    animation::Frames,
+    grid::Grid,
+    parsing::ParseError,
+    point::Point2,
+    registry::Registry,
+    render::Image,
+    solution::{Answer, BenchSettings, Params, Solution},
+};
+use itertools::Itertools;
+use std::{collections::HashSet, time::Duration};
 
 mod moves;
 mod parse;
+mod rope;
 
-fn solve<const N: usize>(file_content: &str) -> usize {
-    let mut rope_x = [0; N];
-    let mut rope_y = [0; N];
+use self::{moves::Move, rope::Rope};
+
+fn solve(file_content: &str, knots: usize) -> Result<usize, ParseError> {
+    let mut rope = Rope::new(knots);
     let mut s = HashSet::new();
 
-    for mut m in parse::parse_moves(file_content) {
+    for m in parse::parse_moves(file_content)? {
         for _ in 0..m.distance {
-            m.apply(&mut rope_x[0], &mut rope_y[0]);
-            for i in 1..N {
-                let dx = rope_x[i - 1] - rope_x[i];
-                let dy = rope_y[i - 1] - rope_y[i];
-                if dy.abs() <= 1 && dx.abs() <= 1 {
-                    continue;
-                }
-                rope_x[i] += dx.signum();
-                rope_y[i] += dy.signum();
+            rope.pull(m.direction);
+            s.insert(rope.tail());
+        }
+    }
+    Ok(s.len())
+}
+
+pub fn solve_task1(file_content: &str) -> Result<usize, ParseError> {
+    solve(file_content, 2)
+}
+pub fn solve_task2(file_content: &str) -> Result<usize, ParseError> {
+    solve(file_content, 10)
+}
+
+/// The rope of the second part moving one step of the head at a time, the frames show the
+/// knots over the squares the tail has visited.
+pub struct RopeFrames {
+    rope: Rope,
+    moves: Vec<Move>,
+    /// Index of the current move and the steps made in it.
+    current: (usize, u32),
+    visited: HashSet<Point2<i32>>,
+    /// The top left corner of the area the head moves in.
+    origin: Point2<i32>,
+    rows: usize,
+    cols: usize,
+}
+
+impl RopeFrames {
+    pub fn new(file_content: &str) -> Result<Self, ParseError> {
+        let moves = parse::parse_moves(file_content)?;
+        let mut head = Point2::default();
+        let mut path = vec![head];
+        for m in &moves {
+            for _ in 0..m.distance {
+                head += m.direction.step();
+                path.push(head);
             }
-            let tail_pos_x = rope_x[N - 1];
-            let tail_pos_y = rope_y[N - 1];
-            s.insert((tail_pos_x, tail_pos_y));
         }
+        // the knots never leave the area of the head
+        let (min_x, max_x) = path.iter().map(|p| p.x).minmax().into_option().unwrap();
+        let (min_y, max_y) = path.iter().map(|p| p.y).minmax().into_option().unwrap();
+        Ok(Self {
+            rope: Rope::new(10),
+            moves,
+            current: (0, 0),
+            visited: HashSet::from([Point2::default()]),
+            origin: Point2::new(min_x, min_y),
+            rows: (max_y - min_y + 1) as usize,
+            cols: (max_x - min_x + 1) as usize,
+        })
+    }
+
+    fn pos(&self, point: Point2<i32>) -> (usize, usize) {
+        let offset = point - self.origin;
+        (offset.y as usize, offset.x as usize)
+    }
+}
+
+impl Frames for RopeFrames {
+    fn frame(&self) -> Image {
+        let mut pixels = Grid::new(self.rows, self.cols, 0);
+        for &point in &self.visited {
+            pixels[self.pos(point)] = 1;
+        }
+        for (ind, &knot) in self.rope.knots().iter().enumerate().rev() {
+            pixels[self.pos(knot)] = if ind == 0 { 3 } else { 2 };
+        }
+        Image::new(pixels).with_palette(vec![
+            [0x10, 0x18, 0x20],
+            [0x3a, 0x5a, 0x7a],
+            [0xd8, 0xb0, 0x60],
+            [0xff, 0x60, 0x40],
+        ])
+    }
+
+    fn step(&mut self) -> bool {
+        let (mut ind, mut steps) = self.current;
+        while ind < self.moves.len() && steps == self.moves[ind].distance {
+            (ind, steps) = (ind + 1, 0);
+        }
+        let Some(m) = self.moves.get(ind) else {
+            return false;
+        };
+        self.rope.pull(m.direction);
+        self.visited.insert(self.rope.tail());
+        self.current = (ind, steps + 1);
+        true
     }
-    s.len()
 }
 
-pub fn solve_task1(file_content: &str) -> usize {
-    solve::<2>(file_content)
+pub struct Day;
+
+impl Solution for Day {
+    fn year(&self) -> u16 {
+        2022
+    }
+    fn day(&self) -> u8 {
+        9
+    }
+    fn title(&self) -> &'static str {
+        "Rope Bridge"
+    }
+    fn part1(&self, file_content: &str, _params: &Params) -> Result<Answer, ParseError> {
+        Ok(solve_task1(file_content)?.into())
+    }
+    fn part2(&self, file_content: &str, _params: &Params) -> Result<Answer, ParseError> {
+        Ok(solve_task2(file_content)?.into())
+    }
+    fn frames(
+        &self,
+        file_content: &str,
+        _params: &Params,
+    ) -> Option<Result<Box<dyn Frames>, ParseError>> {
+        Some(RopeFrames::new(file_content).map(|frames| Box::new(frames) as _))
+    }
+    fn bench_settings(&self) -> BenchSettings {
+        BenchSettings {
+            measurement_time: Duration::from_secs(6),
+            ..Default::default()
+        }
+    }
 }
-pub fn solve_task2(file_content: &str) -> usize {
-    solve::<10>(file_content)
+
+pub fn register(registry: &mut Registry) {
+    registry.register(&Day);
 }
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::fs;
     const INPUT: &str = "R 4
 U 4
 L 3
@@ -49,21 +164,7 @@ R 2";
     #[test]
     #[ignore]
     fn test_y22_d9_t1() {
-        assert_eq!(format!("{}", solve_task1(INPUT)), "13");
-    }
-    #[test]
-    #[ignore]
-    fn test_y22_d9_t1_actual() {
-        let str = fs::read_to_string("./benches/y22d9.txt").unwrap_or_default();
-        let res = solve_task1(&str);
-        assert_eq!(res, 6067);
-    }
-    #[test]
-    #[ignore]
-    fn test_y22_d9_t2_actual() {
-        let str = fs::read_to_string("./benches/y22d9.txt").unwrap_or_default();
-        let res = solve_task2(&str);
-        assert_eq!(res, 2471);
+        assert_eq!(format!("{}", solve_task1(INPUT).unwrap()), "13");
     }
     #[test]
     #[ignore]
@@ -81,6 +182,7 @@ D 10
 L 25
 U 20"
                 )
+                .unwrap()
             ),
             "36"
         );
@@ -88,6 +190,30 @@ U 20"
     #[test]
     #[ignore]
     fn test_task2_2() {
-        assert_eq!(format!("{}", solve_task2(INPUT)), "1");
+        assert_eq!(format!("{}", solve_task2(INPUT).unwrap()), "1");
+    }
+
+    #[test]
+    fn test_invalid_move() {
+        assert_eq!(
+            solve_task1("R 4\nLL 3").unwrap_err().to_string(),
+            "line 2, column 2: expected space, found \"L 3\""
+        );
+        assert_eq!(
+            solve_task1("X 3").unwrap_err().to_string(),
+            "line 1, column 1: expected direction, found \"X 3\""
+        );
+    }
+
+    #[test]
+    #[ignore]
+    fn test_frames() {
+        let mut frames = RopeFrames::new(INPUT).unwrap();
+        while frames.step() {}
+        let image = frames.frame();
+        assert_eq!((image.width(), image.height()), (6, 5));
+        // the tail stays at the start, under the other knots
+        assert_eq!(frames.visited.len(), 1);
+        assert_eq!(image.pixels()[(4, 0)], 2);
     }
 }
@@ -1,18 +1,13 @@
-pub(crate) enum Direction {
-    Up,
-    Right,
-    Down,
-    Left,
-}
+use crate::point::Dir4;
 
 pub struct Move {
-    pub(crate) direction: Direction,
+    pub(crate) direction: Dir4,
     pub(crate) distance: u32,
 }
 
 impl std::fmt::Debug for Move {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        use Direction::*;
+        use Dir4::*;
         match self.direction {
             Up => write!(f, "U {}", self.distance),
             Right => write!(f, "R {}", self.distance),
@@ -21,24 +16,3 @@ impl std::fmt::Debug for Move {
         }
     }
 }
-
-impl Move {
-    #[inline]
-    pub fn apply(&mut self, x: &mut i32, y: &mut i32) {
-        match self.direction {
-            Direction::Up => {
-                *y -= 1;
-            }
-            Direction::Right => {
-                *x += 1;
-            }
-            Direction::Down => {
-                *y += 1;
-            }
-            Direction::Left => {
-                *x -= 1;
-            }
-        }
-        self.distance -= 1;
-    }
-}
@@ -1,38 +1,39 @@
-use nom::{self, IResult};
+use nom::{
+    character::complete::{self, one_of, space1},
+    combinator::map_opt,
+    error::context,
+    sequence::separated_pair,
+};
 
-use super::moves::{Direction, Move};
+use super::moves::Move;
+use crate::{
+    parsing::{parse_lines, IResult, ParseError},
+    point::Dir4,
+};
 
-fn try_parse_move(line: &str) -> IResult<&str, Move> {
-    nom::combinator::map(
-        nom::sequence::separated_pair(
-            nom::bytes::complete::is_a("LRUD"),
-            nom::character::complete::space1,
-            nom::character::complete::u32,
-        ),
-        |(a, distance): (&str, u32)| match a {
-            "L" => Move {
-                direction: Direction::Left,
-                distance,
-            },
-            "U" => Move {
-                direction: Direction::Up,
-                distance,
-            },
-            "R" => Move {
-                direction: Direction::Right,
-                distance,
-            },
-            "D" => Move {
-                direction: Direction::Down,
-                distance,
-            },
-            _ => unreachable!(),
+fn parse_direction(input: &str) -> IResult<'_, Dir4> {
+    let direction = map_opt(one_of("LRUD"), |letter| match letter {
+        'L' => Some(Dir4::Left),
+        'U' => Some(Dir4::Up),
+        'R' => Some(Dir4::Right),
+        'D' => Some(Dir4::Down),
+        _ => None,
+    });
+    context("direction", direction)(input)
+}
+
+fn parse_move(line: &str) -> IResult<'_, Move> {
+    let (input, (direction, distance)) =
+        separated_pair(parse_direction, space1, complete::u32)(line)?;
+    Ok((
+        input,
+        Move {
+            direction,
+            distance,
         },
-    )(line)
+    ))
 }
 
-pub fn parse_moves(input: &str) -> impl Iterator<Item = Move> + '_ {
-    input
-        .lines()
-        .map(|line| try_parse_move(line).map(|x| x.1).unwrap())
+pub fn parse_moves(input: &str) -> Result<Vec<Move>, ParseError> {
+    parse_lines(input, parse_move).collect()
 }
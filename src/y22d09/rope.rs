@@ -0,0 +1,36 @@
+use crate::point::{Dir4, Point2};
+
+/// Knots of the rope, the head is the first one.
+#[derive(Debug, Clone)]
+pub struct Rope {
+    knots: Vec<Point2<i32>>,
+}
+
+impl Rope {
+    pub fn new(knots: usize) -> Self {
+        Self {
+            knots: vec![Point2::default(); knots],
+        }
+    }
+
+    pub fn knots(&self) -> &[Point2<i32>] {
+        &self.knots
+    }
+
+    pub fn tail(&self) -> Point2<i32> {
+        *self.knots.last().unwrap()
+    }
+
+    /// Moves the head one step, every other knot follows the previous one once they stop
+    /// touching.
+    pub fn pull(&mut self, direction: Dir4) {
+        self.knots[0] += direction.step();
+        for i in 1..self.knots.len() {
+            let diff = self.knots[i - 1] - self.knots[i];
+            if diff.chebyshev(Point2::default()) <= 1 {
+                break;
+            }
+            self.knots[i] += diff.signum();
+        }
+    }
+}
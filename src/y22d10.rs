@@ -3,31 +3,93 @@ mod cpu;
 mod crt;
 mod parse;
 
-use cpu::CPU;
-use crt::CRT;
+use crate::{
+    ocr::{self, OcrError},
+    parsing::ParseError,
+    registry::Registry,
+    render::{Image, Rgb},
+    solution::{Answer, Params, Solution},
+    trace::Trace,
+    vm::{Breakpoint, Stop, Vm},
+};
+use cpu::Cpu;
+use crt::Crt;
 use parse::parse_commands;
 
-pub fn solve_task1(file_content: &str) -> i32 {
-    CPU::new(parse_commands(file_content))
-        .enumerate()
-        .map(|(ind, register)| ((ind + 1) as i32, register))
-        .filter(|(cycle, _)| *cycle % 40 == 20)
-        .map(|(cycle, register)| cycle * register)
-        .sum()
+pub fn solve_task1(file_content: &str, trace: &Trace) -> Result<i64, ParseError> {
+    let mut vm = Vm::<Cpu>::new(parse_commands(file_content)?).with_trace(trace.clone());
+    for cycle in (20..=220).step_by(40) {
+        vm.add_breakpoint(Breakpoint::Cycle(cycle));
+    }
+    let mut sum = 0;
+    while let Stop::Breakpoint(_) = vm.run() {
+        sum += vm.cycle() as i64 * vm.registers()["x"];
+    }
+    Ok(sum)
 }
 
-pub fn solve_task2(file_content: &str) -> String {
-    CPU::new(parse_commands(file_content))
-        .scan(CRT::new(), |c, r| Some(c.draw(r)))
-        .collect::<String>()
+/// Returns the picture on the CRT screen.
+pub fn draw(file_content: &str, trace: &Trace) -> Result<String, ParseError> {
+    let mut vm = Vm::<Cpu>::new(parse_commands(file_content)?).with_trace(trace.clone());
+    let mut crt = Crt::new();
+    let mut screen = String::new();
+    while vm.step().is_some() {
+        screen += crt.draw(vm.registers()["x"] as i32);
+    }
+    Ok(screen)
 }
+
+/// Returns the letters on the screen, or the screen itself if some letter is not known,
+/// so that it is still readable by a human.
+pub fn solve_task2(file_content: &str, trace: &Trace) -> Result<Answer, ParseError> {
+    let screen = draw(file_content, trace)?;
+    match ocr::recognize(&screen) {
+        Ok(letters) => Ok(letters.into()),
+        Err(OcrError::UnknownGlyph { index, column, .. }) => {
+            trace.event("unknown glyph", &[("index", &index), ("column", &column)]);
+            Ok(screen.into())
+        }
+        Err(OcrError::Height(_)) => Ok(screen.into()),
+    }
+}
+
+/// Dark and lit pixels of the CRT.
+const SCREEN_PALETTE: [Rgb; 2] = [[0x0f, 0x0f, 0x23], [0x33, 0xff, 0x33]];
+
+pub struct Day;
+
+impl Solution for Day {
+    fn year(&self) -> u16 {
+        2022
+    }
+    fn day(&self) -> u8 {
+        10
+    }
+    fn title(&self) -> &'static str {
+        "Cathode-Ray Tube"
+    }
+    fn part1(&self, file_content: &str, params: &Params) -> Result<Answer, ParseError> {
+        Ok(solve_task1(file_content, params.trace())?.into())
+    }
+    fn part2(&self, file_content: &str, params: &Params) -> Result<Answer, ParseError> {
+        solve_task2(file_content, params.trace())
+    }
+    fn image(&self, file_content: &str, params: &Params) -> Option<Result<Image, ParseError>> {
+        Some(draw(file_content, params.trace()).map(|screen| {
+            Image::from_text(&screen, |c| u8::from(c == '#')).with_palette(SCREEN_PALETTE.to_vec())
+        }))
+    }
+}
+
+pub fn register(registry: &mut Registry) {
+    registry.register(&Day);
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::fs;
-
     const INPUT: &str = "addx 15
-    addx -11
+addx -11
 addx 6
 addx -3
 addx 5
@@ -175,14 +237,10 @@ noop";
     #[test]
     #[ignore]
     fn test_task1() {
-        assert_eq!(format!("{}", solve_task1(INPUT)), "13140");
-    }
-    #[test]
-    #[ignore]
-    fn test_task1_actual() {
-        let str = fs::read_to_string("./benches/y22d10.txt").unwrap_or_default();
-
-        assert_eq!(format!("{}", solve_task1(&str)), "14060");
+        assert_eq!(
+            format!("{}", solve_task1(INPUT, &Trace::default()).unwrap()),
+            "13140"
+        );
     }
 
     #[test]
@@ -194,17 +252,28 @@ noop";
                 solve_task1(
                     "noop
 addx 3
-addx -5"
+addx -5",
+                    &Trace::default()
                 )
+                .unwrap()
             ),
             "0"
         );
     }
 
+    #[test]
+    #[ignore]
+    fn test_task2_stripes() {
+        assert_eq!(
+            solve_task2(INPUT, &Trace::default()).unwrap(),
+            Answer::Art(draw(INPUT, &Trace::default()).unwrap())
+        );
+    }
+
     #[test]
     #[ignore]
     fn test_task2() {
-        let res = solve_task2(INPUT);
+        let res = draw(INPUT, &Trace::default()).unwrap();
         assert_eq!(
             res,
             "##..##..##..##..##..##..##..##..##..##..
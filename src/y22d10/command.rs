@@ -3,3 +3,12 @@ pub enum Command {
     Noop,
     Addx(i32),
 }
+
+impl std::fmt::Display for Command {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Command::Noop => write!(f, "noop"),
+            Command::Addx(delta) => write!(f, "addx {delta}"),
+        }
+    }
+}
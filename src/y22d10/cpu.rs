@@ -1,70 +1,24 @@
 use super::command::Command;
+use crate::vm::{InstructionSet, Registers};
 
-#[derive(Clone, Copy, Debug)]
-pub struct Current {
-    command: Command,
-    start: usize,
-}
+/// Instruction set of the handheld device, `x` is the sprite position the CRT reads.
+pub struct Cpu;
 
-pub struct CPU<Cmds>
-where
-    Cmds: Iterator<Item = Command>,
-{
-    commands: Cmds,
-    cycle: usize,
-    current: Option<Current>,
-    x: i32,
-}
+impl InstructionSet for Cpu {
+    type Instruction = Command;
+    const REGISTERS: &'static [(&'static str, i64)] = &[("x", 1)];
 
-impl<Cmd> CPU<Cmd>
-where
-    Cmd: Iterator<Item = Command>,
-{
-    pub fn new(cmds: Cmd) -> Self {
-        Self {
-            commands: cmds,
-            cycle: 0,
-            current: None,
-            x: 1,
+    fn cycles(command: &Command) -> usize {
+        match command {
+            Command::Noop => 1,
+            Command::Addx(_) => 2,
         }
     }
-}
-
-impl<Cmds> Iterator for CPU<Cmds>
-where
-    Cmds: Iterator<Item = Command>,
-{
-    type Item = i32;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        match self.current {
-            Some(Current { command, start }) => match command {
-                Command::Noop => {
-                    self.cycle += 1;
-                    self.current = None;
-                    Some(self.x)
-                }
-                Command::Addx(delta) => {
-                    self.cycle += 1;
-                    let before = self.x;
-                    if start + 2 == self.cycle {
-                        self.x += delta;
-                        self.current = None;
-                    }
 
-                    Some(before)
-                }
-            },
-            None => match self.commands.next() {
-                Some(cmd) => {
-                    self.current = Some(Current {
-                        command: cmd,
-                        start: self.cycle,
-                    });
-                    self.next()
-                }
-                None => None,
-            },
+    fn execute(command: &Command, registers: &mut Registers) -> isize {
+        if let Command::Addx(delta) = command {
+            registers["x"] += *delta as i64;
         }
+        1
     }
 }
@@ -1,9 +1,9 @@
-pub struct CRT {
+pub struct Crt {
     pub row: usize,
     pub col: usize,
 }
 
-impl CRT {
+impl Crt {
     pub fn new() -> Self {
         Self { row: 0, col: 0 }
     }
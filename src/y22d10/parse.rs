@@ -1,19 +1,14 @@
 use super::command::Command;
-use nom::IResult;
+use crate::parsing::{parse_lines, tag, IResult, ParseError};
+use nom::{branch::alt, character::complete, combinator::map, sequence::preceded};
 
-fn parse_command(line: &str) -> IResult<&str, Command> {
-    nom::branch::alt((
-        nom::combinator::map(nom::bytes::complete::tag("noop"), |_| Command::Noop),
-        nom::combinator::map(
-            nom::sequence::preceded(
-                nom::bytes::complete::tag("addx "),
-                nom::character::complete::i32,
-            ),
-            |res| Command::Addx(res),
-        ),
+fn parse_command(line: &str) -> IResult<'_, Command> {
+    alt((
+        map(tag("noop"), |_| Command::Noop),
+        map(preceded(tag("addx "), complete::i32), Command::Addx),
     ))(line)
 }
 
-pub fn parse_commands(input: &str) -> impl Iterator<Item = Command> + '_ {
-    input.lines().map(|line| parse_command(line).unwrap().1)
+pub fn parse_commands(input: &str) -> Result<Vec<Command>, ParseError> {
+    parse_lines(input, parse_command).collect()
 }
@@ -1,5 +1,8 @@
 use std::collections::HashMap;
 
+/// Expression compiled to a function of the old worry level.
+pub type Operation = Box<dyn Fn(u64) -> u64 + Send + Sync>;
+
 #[derive(Debug)]
 pub enum Expression<'input> {
     Var(&'input str),
@@ -11,10 +14,44 @@ pub enum Expression<'input> {
 impl<'input> Expression<'input> {
     pub fn calculate(&self, vars: &HashMap<&'input str, u64>) -> u64 {
         match self {
-            Expression::Var(v) => vars.get(v).map(|x| *x).unwrap_or_default(),
+            Expression::Var(v) => vars.get(v).copied().unwrap_or_default(),
             Expression::Integer(i) => *i,
             Expression::Sum(pair) => pair.0.calculate(vars) + pair.1.calculate(vars),
             Expression::Product(pair) => pair.0.calculate(vars) * pair.1.calculate(vars),
         }
     }
+
+    /// Compiles the expression of the variable `old`, the only one the parser reads.
+    pub fn compile(&self) -> Operation {
+        match self {
+            Expression::Var(var) => {
+                assert_eq!(*var, "old", "unknown variable");
+                Box::new(|old| old)
+            }
+            &Expression::Integer(i) => Box::new(move |_| i),
+            Expression::Sum(pair) => match &**pair {
+                (Expression::Var("old"), Expression::Integer(i))
+                | (Expression::Integer(i), Expression::Var("old")) => {
+                    let i = *i;
+                    Box::new(move |old| old + i)
+                }
+                (a, b) => {
+                    let (a, b) = (a.compile(), b.compile());
+                    Box::new(move |old| a(old) + b(old))
+                }
+            },
+            Expression::Product(pair) => match &**pair {
+                (Expression::Var("old"), Expression::Var("old")) => Box::new(|old| old * old),
+                (Expression::Var("old"), Expression::Integer(i))
+                | (Expression::Integer(i), Expression::Var("old")) => {
+                    let i = *i;
+                    Box::new(move |old| old * i)
+                }
+                (a, b) => {
+                    let (a, b) = (a.compile(), b.compile());
+                    Box::new(move |old| a(old) * b(old))
+                }
+            },
+        }
+    }
 }
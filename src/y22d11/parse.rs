@@ -1,21 +1,24 @@
 use super::{condition::Condition, item::Item, monkey::Monkey, operation::Expression};
+use crate::{
+    input::{blocks, parse_blocks},
+    parsing::{tag, Error, Expected, IResult, ParseError},
+};
 use nom::{
     branch::alt,
-    bytes::complete::tag,
     character::{self, complete::newline},
-    combinator,
+    combinator::{self, map_opt, verify},
+    error::context,
     multi::separated_list1,
-    sequence::{self, delimited, preceded, separated_pair, tuple},
-    IResult,
+    sequence::{delimited, preceded, separated_pair, tuple},
 };
 
-fn parse_index(input: &str) -> IResult<&str, u64> {
+fn parse_index(input: &str) -> IResult<'_, u64> {
     let mut parse_line =
         nom::sequence::delimited(tag("Monkey "), character::complete::u64, tag(":\n"));
     parse_line(input)
 }
 
-fn parse_items(input: &str) -> IResult<&str, Vec<Item>> {
+fn parse_items(input: &str) -> IResult<'_, Vec<Item>> {
     let mut parse = nom::sequence::delimited(
         tag("  Starting items: "),
         separated_list1(
@@ -27,31 +30,31 @@ fn parse_items(input: &str) -> IResult<&str, Vec<Item>> {
     parse(input)
 }
 
-fn parse_int<'t>(input: &'t str) -> IResult<&'t str, Expression<'t>> {
-    let mut parse_int = combinator::map(character::complete::u64, |v| Expression::Integer(v));
+fn parse_int<'t>(input: &'t str) -> IResult<'t, Expression<'t>> {
+    let mut parse_int = combinator::map(character::complete::u64, Expression::Integer);
     parse_int(input)
 }
-fn parse_old<'t>(input: &'t str) -> IResult<&'t str, Expression<'t>> {
+fn parse_old<'t>(input: &'t str) -> IResult<'t, Expression<'t>> {
     let mut parse_old = combinator::map(tag("old"), |_| Expression::Var("old"));
     parse_old(input)
 }
-fn parse_sum<'t>(input: &'t str) -> IResult<&'t str, Expression<'t>> {
+fn parse_sum<'t>(input: &'t str) -> IResult<'t, Expression<'t>> {
     combinator::map(
-        separated_pair(parse_expression, tag(" + "), parse_expression),
+        separated_pair(parse_operand, tag(" + "), parse_operand),
         |(a, b)| Expression::Sum(Box::new((a, b))),
     )(input)
 }
-fn parse_product<'t>(input: &'t str) -> IResult<&'t str, Expression<'t>> {
+fn parse_product<'t>(input: &'t str) -> IResult<'t, Expression<'t>> {
     combinator::map(
-        separated_pair(parse_expression, tag(" * "), parse_expression),
+        separated_pair(parse_operand, tag(" * "), parse_operand),
         |(a, b)| Expression::Product(Box::new((a, b))),
     )(input)
 }
-fn parse_expression<'t>(input: &'t str) -> IResult<&'t str, Expression<'t>> {
-    alt((parse_int, parse_old, parse_sum, parse_product))(input)
+fn parse_operand<'t>(input: &'t str) -> IResult<'t, Expression<'t>> {
+    alt((parse_int, parse_old))(input)
 }
 
-fn parse_operation(input: &str) -> IResult<&str, Expression> {
+fn parse_operation(input: &str) -> IResult<'_, Expression<'_>> {
     let mut parse = nom::sequence::preceded(
         tag("  Operation: new = "),
         alt((
@@ -64,38 +67,60 @@ fn parse_operation(input: &str) -> IResult<&str, Expression> {
     parse(input)
 }
 
-fn parse_condition(input: &str) -> IResult<&str, Condition> {
+fn parse_divisor(input: &str) -> IResult<'_, u64> {
+    context(
+        "divisor above 0",
+        verify(character::complete::u64, |&divisor| divisor > 0),
+    )(input)
+}
+
+/// Parses the index of one of the `count` monkeys.
+fn parse_target<'a>(count: usize) -> impl FnMut(&'a str) -> IResult<'a, usize> {
+    let target = map_opt(character::complete::u32, move |target| {
+        Some(target as usize).filter(|&target| target < count)
+    });
+    context("monkey number", target)
+}
+
+fn parse_condition<'a>(count: usize) -> impl FnMut(&'a str) -> IResult<'a, Condition> {
     nom::combinator::map(
         tuple((
-            delimited(
-                tag("  Test: divisible by "),
-                character::complete::u64,
-                newline,
-            ),
+            delimited(tag("  Test: divisible by "), parse_divisor, newline),
             delimited(
                 tag("    If true: throw to monkey "),
-                character::complete::u32,
+                parse_target(count),
                 newline,
             ),
-            preceded(
-                tag("    If false: throw to monkey "),
-                character::complete::u32,
-            ),
+            preceded(tag("    If false: throw to monkey "), parse_target(count)),
         )),
         |(divisor, if_true, if_false)| {
-            Condition::new_division_condition(divisor, if_true as usize, if_false as usize)
+            Condition::new_division_condition(divisor, if_true, if_false)
         },
-    )(input)
+    )
 }
 
-fn parse_monkey(input: &str) -> IResult<&str, Monkey> {
-    let parse_tuple = tuple((parse_index, parse_items, parse_operation, parse_condition));
-    let mut parse = nom::combinator::map(parse_tuple, |(_, items, operation, condition)| {
+/// Parses one of the `count` monkeys.
+fn parse_monkey<'a>(count: usize) -> impl FnMut(&'a str) -> IResult<'a, Monkey<'a>> {
+    let parse_tuple = tuple((
+        parse_index,
+        parse_items,
+        parse_operation,
+        parse_condition(count),
+    ));
+    nom::combinator::map(parse_tuple, |(_, items, operation, condition)| {
         Monkey::new(items, operation, condition)
-    });
-
-    parse(input)
+    })
 }
-pub(crate) fn parse_monkeys(input: &str) -> IResult<&str, Vec<Monkey>> {
-    separated_list1(sequence::pair(newline, newline), parse_monkey)(input)
+/// Parses the monkeys, there are at least two of them to find the most active pair and
+/// they throw the items only to each other.
+pub(crate) fn parse_monkeys(input: &str) -> Result<Vec<Monkey<'_>>, ParseError> {
+    let count = blocks(input).count();
+    if count < 2 {
+        let end = &input[input.len()..];
+        return Err(ParseError::new(
+            input,
+            Error::new(end, Expected::Context("another monkey")),
+        ));
+    }
+    parse_blocks(input, parse_monkey(count)).collect()
 }
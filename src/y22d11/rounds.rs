@@ -0,0 +1,51 @@
+//! The simulation of the monkeys round by round, kept as the baseline of the benchmark of
+//! the item by item one.
+use super::parse;
+use crate::{math::lcm, parsing::ParseError};
+use std::cell::RefCell;
+
+pub fn solve(file_content: &str, rounds: u64, divider: u64) -> Result<u64, ParseError> {
+    let monkeys = parse::parse_monkeys(file_content)?
+        .into_iter()
+        .map(RefCell::new)
+        .collect::<Vec<_>>();
+
+    let base = monkeys
+        .iter()
+        .map(|x| x.borrow().condition.divisor)
+        .fold(1, lcm);
+
+    let monkeys_len = monkeys.len();
+
+    for _ in 0..rounds {
+        for i in 0..monkeys_len {
+            let transfers = {
+                let mut monkey = monkeys[i].borrow_mut();
+                std::mem::take(&mut monkey.items)
+                    .into_iter()
+                    .map(|mut item| {
+                        monkey.inspect(&mut item, divider, base);
+                        let next_monkey_index = monkey.choose(&item);
+                        (next_monkey_index, item)
+                    })
+                    .collect::<Vec<_>>()
+            };
+            for (to, what) in transfers {
+                let mut monkey = monkeys[to].borrow_mut();
+                monkey.catch(what);
+            }
+        }
+    }
+
+    let mut inspected = monkeys
+        .iter()
+        .map(|x| x.borrow().inspected)
+        .collect::<Vec<_>>();
+
+    inspected.sort_by(|a, b| b.cmp(a));
+
+    let a = inspected[0];
+    let b = inspected[1];
+
+    Ok(a * b)
+}
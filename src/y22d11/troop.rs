@@ -0,0 +1,108 @@
+use super::{item::Item, monkey::Monkey, operation::Operation};
+use crate::{cycle::Cycle, math::lcm};
+use std::collections::HashMap;
+
+/// What a monkey does with the items, compiled for the simulation of single items.
+struct Rule {
+    operation: Operation,
+    divisor: u64,
+    if_true: usize,
+    if_false: usize,
+}
+
+/// The monkeys with the items they hold at the start. The items never affect each other, so
+/// every item is followed through the monkeys on its own.
+pub struct Troop {
+    rules: Vec<Rule>,
+    /// The items with the indices of the monkeys holding them.
+    items: Vec<(usize, Item)>,
+    divider: u64,
+    /// The lcm of the divisors, the worry levels are kept modulo it.
+    modulus: u64,
+}
+
+impl Troop {
+    pub fn new(monkeys: &[Monkey], divider: u64) -> Self {
+        let rules = monkeys
+            .iter()
+            .map(|monkey| Rule {
+                operation: monkey.operation.compile(),
+                divisor: monkey.condition.divisor,
+                if_true: monkey.condition.if_true,
+                if_false: monkey.condition.if_false,
+            })
+            .collect::<Vec<_>>();
+        let items = monkeys
+            .iter()
+            .enumerate()
+            .flat_map(|(ind, monkey)| monkey.items.iter().map(move |&item| (ind, item)))
+            .collect();
+        let modulus = rules.iter().map(|rule| rule.divisor).fold(1, lcm);
+        Self {
+            rules,
+            items,
+            divider,
+            modulus,
+        }
+    }
+
+    /// Returns the number of the items every monkey inspects during the rounds.
+    pub fn inspections(&self, rounds: usize) -> Vec<u64> {
+        let mut res = vec![0; self.rules.len()];
+        for &item in &self.items {
+            for (total, count) in res.iter_mut().zip(self.item_inspections(item, rounds)) {
+                *total += count;
+            }
+        }
+        res
+    }
+
+    /// Follows the item held by the monkey through the rounds and counts its inspections by
+    /// every monkey. Once the item is back at the same monkey with the same worry level at the
+    /// start of a round, the remaining rounds repeat the ones since then.
+    fn item_inspections(&self, mut item: (usize, Item), rounds: usize) -> Vec<u64> {
+        let n = self.rules.len();
+        let mut seen = HashMap::new();
+        // the inspections made before every round, n counts per round
+        let mut history = vec![0; n];
+        for round in 0..rounds {
+            if let Some(start) = seen.insert(item, round) {
+                let cycle = Cycle {
+                    start,
+                    length: round - start,
+                };
+                let counts = |round: usize| &history[round * n..(round + 1) * n];
+                let cycles = ((rounds - start) / cycle.length) as u64;
+                return (counts(cycle.reduce(rounds)).iter())
+                    .zip(counts(round).iter().zip(counts(start)))
+                    .map(|(&reduced, (&end, &start))| reduced + (end - start) * cycles)
+                    .collect();
+            }
+            let mut counts = history[round * n..].to_vec();
+            item = self.round(item, &mut counts);
+            history.extend(counts);
+        }
+        history.split_off(rounds * n)
+    }
+
+    /// Passes the item through the monkeys for one round, returns where it is at the start of
+    /// the next one. An item thrown to a monkey further in the order is inspected again in the
+    /// same round.
+    fn round(&self, (mut monkey, mut item): (usize, Item), counts: &mut [u64]) -> (usize, Item) {
+        loop {
+            let rule = &self.rules[monkey];
+            counts[monkey] += 1;
+            let increased = (rule.operation)(item.worry_level) % self.modulus;
+            item.worry_level = increased / self.divider % self.modulus;
+            let next = if item.worry_level.is_multiple_of(rule.divisor) {
+                rule.if_true
+            } else {
+                rule.if_false
+            };
+            if next <= monkey {
+                return (next, item);
+            }
+            monkey = next;
+        }
+    }
+}
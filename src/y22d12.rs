@@ -1,131 +1,210 @@
-use itertools::Itertools;
-use std::{collections::VecDeque, ops::RangeInclusive};
+use crate::{
+    animation::Frames,
+    grid::{Grid, Pos},
+    parsing::{Error, Expected, ParseError},
+    registry::Registry,
+    render::{gradient, Image, Rgb},
+    search::{bfs_in, Bfs, Successors, Visit},
+    solution::{Answer, Params, Solution},
+};
+use std::ops::RangeInclusive;
+
+pub fn solve_task1(file_content: &str) -> Result<usize, ParseError> {
+    let (grid, start, end) = parse_grid(file_content)?;
+
+    Ok(shortest_climb(
+        &grid,
+        start,
+        |height| 0..=height + 1,
+        |pos| pos == end,
+    ))
+}
 
-pub fn solve_task1(file_content: &str) -> usize {
-    let (grid, start, end) = parse_grid(file_content);
+pub fn solve_task2(file_content: &str) -> Result<usize, ParseError> {
+    let (grid, _, start) = parse_grid(file_content)?;
 
-    let minimal_distances = calculate_min_distances(&grid, start, |height| 0..=height + 1);
+    // going down from the end, the first square at the lowest level is the closest one
+    Ok(shortest_climb(
+        &grid,
+        start,
+        |current_height| current_height.saturating_sub(1)..=END_VALUE,
+        |pos| grid[pos] == START_VALUE,
+    ))
+}
 
-    minimal_distances[end.0][end.1]
+/// Returns the number of steps to the closest position satisfying `is_goal`, `usize::MAX`
+/// if none of them can be reached.
+pub fn shortest_climb(
+    grid: &HeightMap,
+    start: Pos,
+    get_destination_height_range: impl Fn(usize) -> RangeInclusive<usize>,
+    is_goal: impl Fn(Pos) -> bool,
+) -> usize {
+    climb(grid, start, get_destination_height_range)
+        .find(|&(pos, _)| is_goal(pos))
+        .map_or(usize::MAX, |(_, steps)| steps)
 }
 
-pub fn solve_task2(file_content: &str) -> usize {
-    let (grid, _, start) = parse_grid(file_content);
-    let rows = grid.len();
-    let cols = grid[0].len();
-
-    let minimal_distances = calculate_min_distances(&grid, start, |current_height| {
-        current_height.saturating_sub(1)..=END_VALUE
-    });
-
-    (0..rows)
-        .cartesian_product(0..cols)
-        .filter(|(a, b)| grid[*a][*b] == START_VALUE)
-        .map(|(a, b)| minimal_distances[a][b])
-        .min()
-        .unwrap_or(usize::MAX)
+type ClimbVisits = Grid<Option<Visit<Pos, usize>>>;
+
+/// Searches the squares reachable from the start, a step can go to the heights in the range
+/// given for the current height.
+fn climb<'a>(
+    grid: &'a HeightMap,
+    start: Pos,
+    get_destination_height_range: impl Fn(usize) -> RangeInclusive<usize> + 'a,
+) -> Bfs<Pos, impl Successors<Pos> + 'a, ClimbVisits> {
+    let successors = move |&pos: &Pos| {
+        let available_range = get_destination_height_range(grid[pos]);
+        grid.neighbours4(pos)
+            .filter(move |next| available_range.contains(&grid[*next]))
+    };
+    bfs_in(
+        Grid::new(grid.rows(), grid.cols(), None),
+        [start],
+        successors,
+    )
 }
 
-pub fn calculate_min_distances(
-    grid: &[Vec<usize>],
-    start: (usize, usize),
-    get_destination_height_range: impl Fn(usize) -> RangeInclusive<usize>,
-) -> Vec<Vec<usize>> {
-    let rows = grid.len();
-    let cols = grid[0].len();
-
-    let mut visited = grid
-        .iter()
-        .map(|v| vec![false; v.len()])
-        .collect::<Vec<_>>();
-    let mut minimal_distance = grid
-        .iter()
-        .map(|v| vec![usize::MAX; v.len()])
-        .collect::<Vec<_>>();
-    let mut tasks: VecDeque<(usize, usize)> = VecDeque::new();
-
-    // invariants:
-    //   visited[i] is true if the node was already visited and minimal distance was calculated to all neighbours
-    //   minimal_distance[i] - contains minimal distance to the node if the node was already visited
-    //   tasks - contains a list of visited nodes which neighbors were potentially not visited.
-
-    minimal_distance[start.0][start.1] = 0;
-
-    tasks.push_back(start);
-
-    while tasks.len() > 0 {
-        let Some((row, col)) = tasks.pop_front() else {
-            unreachable!();
-        };
-        if visited[row][col] {
-            continue;
+/// Draws the height map with the shortest path from the start to the end.
+pub fn draw(file_content: &str) -> Result<Image, ParseError> {
+    let (grid, start, end) = parse_grid(file_content)?;
+    let mut search = climb(&grid, start, |height| 0..=height + 1).with_paths();
+    let _ = search.find(|&(pos, _)| pos == end);
+    let path_color = ALPHABET.len() as u8;
+    let mut pixels = grid.map(|&height| height as u8);
+    for pos in search.tree().path(&end).into_iter().flatten() {
+        pixels[pos] = path_color;
+    }
+    Ok(Image::new(pixels).with_palette(palette()))
+}
+
+/// Colors of the heights followed by the colors of the path, the searched squares and the
+/// frontier.
+fn palette() -> Vec<Rgb> {
+    let mut palette = gradient([0x1b, 0x4d, 0x1b], [0xf0, 0xf0, 0xf0], ALPHABET.len());
+    palette.extend([[0xe0, 0x20, 0x20], [0x30, 0x60, 0xa0], [0x60, 0xc0, 0xff]]);
+    palette
+}
+
+/// The search of the first part going one step further from the start at a time, the
+/// frames show the searched squares and the frontier over the height map.
+pub struct ClimbFrames {
+    grid: HeightMap,
+    /// The squares in the order they are reached with their distances from the start.
+    visits: Vec<(Pos, usize)>,
+    /// Number of the squares reached so far.
+    reached: usize,
+}
+
+impl ClimbFrames {
+    pub fn new(file_content: &str) -> Result<Self, ParseError> {
+        let (grid, start, end) = parse_grid(file_content)?;
+        let mut visits = Vec::new();
+        for (pos, steps) in climb(&grid, start, |height| 0..=height + 1) {
+            visits.push((pos, steps));
+            if pos == end {
+                break;
+            }
         }
-        let current_height = grid[row][col];
-        let available_range = get_destination_height_range(current_height);
-        for (r, c) in get_neighbours(rows, cols, row, col)
-            .into_iter()
-            .filter(|(r, c)| !visited[*r][*c])
-            .filter(|(r, c)| available_range.contains(&grid[*r][*c]))
-        {
-            let min_distance =
-                minimal_distance[r][c].min(minimal_distance[row][col].saturating_add(1));
-            minimal_distance[r][c] = min_distance;
-            tasks.push_back((r, c));
+        Ok(Self {
+            grid,
+            visits,
+            reached: 0,
+        })
+    }
+}
+
+impl Frames for ClimbFrames {
+    fn frame(&self) -> Image {
+        let (searched, frontier) = (ALPHABET.len() as u8 + 1, ALPHABET.len() as u8 + 2);
+        let mut pixels = self.grid.map(|&height| height as u8);
+        let reached = &self.visits[..self.reached];
+        let last = reached.last().map(|&(_, steps)| steps);
+        for &(pos, steps) in reached {
+            pixels[pos] = if Some(steps) == last {
+                frontier
+            } else {
+                searched
+            };
         }
-        visited[row][col] = true;
+        Image::new(pixels).with_palette(palette())
+    }
+
+    fn step(&mut self) -> bool {
+        let Some(&(_, steps)) = self.visits.get(self.reached) else {
+            return false;
+        };
+        self.reached += self.visits[self.reached..]
+            .iter()
+            .take_while(|&&(_, s)| s == steps)
+            .count();
+        true
     }
-    minimal_distance
 }
 
 const ALPHABET: &str = "abcdefghijklmnopqrstuvwxyz";
 const END_VALUE: usize = ALPHABET.len() - 1;
 const START_VALUE: usize = 0;
 
-fn parse_grid(file_content: &str) -> (Vec<Vec<usize>>, (usize, usize), (usize, usize)) {
-    let mut res = Vec::new();
-    let mut start: Option<(usize, usize)> = None;
-    let mut end: Option<(usize, usize)> = None;
-    for (row, line) in file_content.lines().enumerate() {
-        let mut new_line = Vec::with_capacity(line.len());
-        for (col, ch) in line.chars().enumerate() {
-            let v = if ch == 'S' {
-                start = Some((row, col));
-                START_VALUE
-            } else if ch == 'E' {
-                end = Some((row, col));
-                END_VALUE
-            } else {
-                ALPHABET
-                    .chars()
-                    .enumerate()
-                    .find_map(|(i, c)| if c == ch { Some(i) } else { None })
-                    .unwrap_or_default()
-            };
-            new_line.push(v);
+type HeightMap = Grid<usize>;
+
+fn parse_grid(file_content: &str) -> Result<(HeightMap, Pos, Pos), ParseError> {
+    let mut start: Option<Pos> = None;
+    let mut end: Option<Pos> = None;
+    let grid = Grid::parse(file_content, |pos, ch| match ch {
+        'S' => {
+            start = Some(pos);
+            Some(START_VALUE)
         }
-        res.push(new_line);
-    }
-    (res, start.unwrap(), end.unwrap())
+        'E' => {
+            end = Some(pos);
+            Some(END_VALUE)
+        }
+        'a'..='z' => Some(ch as usize - 'a' as usize),
+        _ => None,
+    })?;
+    let missing = |square| {
+        let end = &file_content[file_content.len()..];
+        ParseError::new(file_content, Error::new(end, Expected::Context(square)))
+    };
+    let start = start.ok_or_else(|| missing("start square S"))?;
+    let end = end.ok_or_else(|| missing("end square E"))?;
+    Ok((grid, start, end))
 }
 
-pub fn get_neighbours(rows: usize, cols: usize, row: usize, col: usize) -> Vec<(usize, usize)> {
-    let min_row = row.saturating_sub(1);
-    let max_row = (row + 1).min(rows.saturating_sub(1));
-    let min_col = col.saturating_sub(1);
-    let max_col = (col + 1).min(cols.saturating_sub(1));
-    let mut res = Vec::new();
-    for r in min_row..=max_row {
-        for c in min_col..=max_col {
-            if r == row && c == col {
-                continue;
-            }
-            if r != row && c != col {
-                continue;
-            }
-            res.push((r, c))
-        }
+pub struct Day;
+
+impl Solution for Day {
+    fn year(&self) -> u16 {
+        2022
+    }
+    fn day(&self) -> u8 {
+        12
+    }
+    fn title(&self) -> &'static str {
+        "Hill Climbing Algorithm"
+    }
+    fn part1(&self, file_content: &str, _params: &Params) -> Result<Answer, ParseError> {
+        Ok(solve_task1(file_content)?.into())
+    }
+    fn part2(&self, file_content: &str, _params: &Params) -> Result<Answer, ParseError> {
+        Ok(solve_task2(file_content)?.into())
+    }
+    fn image(&self, file_content: &str, _params: &Params) -> Option<Result<Image, ParseError>> {
+        Some(draw(file_content))
+    }
+    fn frames(
+        &self,
+        file_content: &str,
+        _params: &Params,
+    ) -> Option<Result<Box<dyn Frames>, ParseError>> {
+        Some(ClimbFrames::new(file_content).map(|frames| Box::new(frames) as _))
     }
-    res
+}
+
+pub fn register(registry: &mut Registry) {
+    registry.register(&Day);
 }
 
 #[cfg(test)]
@@ -136,25 +215,38 @@ abcryxxl
 accszExk
 acctuvwj
 abdefghi";
-    const ACTUAL: &str = include_str!("../benches/y22d12.txt");
     #[test]
     #[ignore]
     fn test_task1() {
-        assert_eq!(format!("{}", solve_task1(INPUT)), "31");
+        assert_eq!(format!("{}", solve_task1(INPUT).unwrap()), "31");
     }
     #[test]
     #[ignore]
-    fn test_task1_actual() {
-        assert_eq!(format!("{}", solve_task1(ACTUAL)), "484");
+    fn test_task2() {
+        assert_eq!(format!("{}", solve_task2(INPUT).unwrap()), "29");
     }
+
     #[test]
     #[ignore]
-    fn test_task2() {
-        assert_eq!(format!("{}", solve_task2(INPUT)), "29");
+    fn test_frames() {
+        let mut frames = ClimbFrames::new(INPUT).unwrap();
+        let mut steps = 0;
+        while frames.step() {
+            steps += 1;
+        }
+        // the end is reached at the distance 31 from the start
+        assert_eq!(steps, 32);
     }
+
     #[test]
-    #[ignore]
-    fn test_task2_actual() {
-        assert_eq!(format!("{}", solve_task2(ACTUAL)), "478");
+    fn test_missing_squares() {
+        assert_eq!(
+            solve_task1("").unwrap_err().to_string(),
+            "line 1, column 1: expected start square S, found end of line"
+        );
+        assert_eq!(
+            solve_task2("Sab\ncba").unwrap_err().to_string(),
+            "line 2, column 4: expected end square E, found end of line"
+        );
     }
 }
@@ -1,8 +1,10 @@
-use nom::{
-    bytes::complete::tag, character::complete, multi::separated_list0, sequence::delimited, IResult,
+use crate::{
+    input::{lines, parse_blocks},
+    parsing::{parse_all, tag, IResult, ParseError},
+    registry::Registry,
+    solution::{Answer, Params, Solution},
 };
-
-use crate::reduces::Reduces;
+use nom::{character::complete, multi::separated_list0, sequence::delimited};
 
 #[derive(PartialEq, Eq, Clone)]
 enum PacketData {
@@ -42,8 +44,10 @@ impl Ord for PacketData {
     }
 }
 
-pub fn solve_task1(file_content: &str) -> usize {
-    parse_groups(file_content)
+pub fn solve_task1(file_content: &str) -> Result<usize, ParseError> {
+    let groups = parse_groups(file_content).collect::<Result<Vec<_>, _>>()?;
+    let sum = groups
+        .into_iter()
         .enumerate()
         .filter_map(|(ind, group)| {
             if is_greater_last_sorted(group) {
@@ -52,7 +56,8 @@ pub fn solve_task1(file_content: &str) -> usize {
                 None
             }
         })
-        .sum()
+        .sum();
+    Ok(sum)
 }
 
 fn is_greater_last_sorted<T: Ord>(list: Vec<T>) -> bool {
@@ -64,12 +69,12 @@ fn is_greater_last_sorted<T: Ord>(list: Vec<T>) -> bool {
     true
 }
 
-pub fn solve_task2(file_content: &str) -> usize {
+pub fn solve_task2(file_content: &str) -> Result<usize, ParseError> {
     let mut packets = file_content
         .lines()
         .filter(|line| !line.is_empty())
-        .map(|line| parse_packet_data(line).unwrap().1)
-        .collect::<Vec<_>>();
+        .map(|line| parse_all(file_content, line, parse_packet_data))
+        .collect::<Result<Vec<_>, _>>()?;
 
     let divider_packet_1 = PacketData::List(vec![PacketData::List(vec![PacketData::Integer(2)])]);
     packets.push(divider_packet_1.clone());
@@ -87,60 +92,50 @@ pub fn solve_task2(file_content: &str) -> usize {
         .iter()
         .position(|p| p == &divider_packet_2)
         .unwrap_or_default();
-    (first + 1) * (second + 1)
+    Ok((first + 1) * (second + 1))
 }
 
-fn parse_groups(file_content: &str) -> impl Iterator<Item = Vec<PacketData>> + '_ {
-    file_content.lines().reduces(Vec::new(), |list, line| {
-        if line.is_empty() {
-            false
-        } else {
-            let packet_data = parse_packet_data(line).unwrap().1;
-            list.push(packet_data);
-            true
-        }
-    })
+fn parse_groups(
+    file_content: &str,
+) -> impl Iterator<Item = Result<Vec<PacketData>, ParseError>> + '_ {
+    parse_blocks(file_content, lines(parse_packet_data))
 }
 
-fn parse_packet_data(line: &str) -> IResult<&str, PacketData> {
+fn parse_packet_data(line: &str) -> IResult<'_, PacketData> {
     let parse_list = nom::combinator::map(
         delimited(
             tag("["),
             separated_list0(tag(","), parse_packet_data),
             tag("]"),
         ),
-        |data| PacketData::List(data),
+        PacketData::List,
     );
 
-    let parse_integer = nom::combinator::map(complete::u32, |num| PacketData::Integer(num));
+    let parse_integer = nom::combinator::map(complete::u32, PacketData::Integer);
 
     nom::branch::alt((parse_integer, parse_list))(line)
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    const INPUT: &str = include_str!("./y22d13/example.txt");
-    const ACTUAL: &str = include_str!("../benches/y22d13.txt");
+pub struct Day;
 
-    #[test]
-    #[ignore]
-    fn test_task1() {
-        assert_eq!(format!("{}", solve_task1(INPUT)), "13");
+impl Solution for Day {
+    fn year(&self) -> u16 {
+        2022
+    }
+    fn day(&self) -> u8 {
+        13
     }
-    #[test]
-    #[ignore]
-    fn test_task1_actual() {
-        assert_eq!(format!("{}", solve_task1(ACTUAL)), "5003");
+    fn title(&self) -> &'static str {
+        "Distress Signal"
     }
-    #[test]
-    #[ignore]
-    fn test_task2() {
-        assert_eq!(format!("{}", solve_task2(INPUT)), "140");
+    fn part1(&self, file_content: &str, _params: &Params) -> Result<Answer, ParseError> {
+        Ok(solve_task1(file_content)?.into())
     }
-    #[test]
-    #[ignore]
-    fn test_task2_actual() {
-        assert_eq!(format!("{}", solve_task2(ACTUAL)), "20280");
+    fn part2(&self, file_content: &str, _params: &Params) -> Result<Answer, ParseError> {
+        Ok(solve_task2(file_content)?.into())
     }
 }
+
+pub fn register(registry: &mut Registry) {
+    registry.register(&Day);
+}
@@ -1,157 +1,294 @@
+use crate::{
+    animation::Frames,
+    grid::Grid,
+    parsing::{parse_lines, tag, IResult, ParseError},
+    registry::Registry,
+    render::{Image, Rgb},
+    solution::{Answer, Params, Solution},
+};
 use itertools::Itertools;
-use std::{cell::RefCell, collections::BTreeMap, ops::RangeInclusive};
+use std::{cell::RefCell, ops::RangeInclusive};
 
-use nom::IResult;
-
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 enum Unit {
     Sand,
     Wall,
 }
 
-pub fn solve_task1(file_content: &str) -> impl std::fmt::Display {
-    const SOURCE: (i32, i32) = (500, 0);
-    let (map, y_range) = parse_map(file_content);
-    let map_ref_cell = RefCell::new(map);
-    let mut i: usize = 0;
-    let can_move = |x, y| !map_ref_cell.borrow().contains_key(&(x, y));
-    let should_stop = |_, y| y > *y_range.end();
-    loop {
-        i += 1;
-        match find_rest_sand_position(SOURCE, can_move, should_stop) {
-            None => return i - 1,
-            Some((x, y)) => {
-                map_ref_cell.borrow_mut().insert((x, y), Unit::Sand);
-            }
-        }
-    }
+pub fn solve_task1(file_content: &str) -> Result<usize, ParseError> {
+    Ok(pour_sand(file_content, false)?.1)
+}
+
+pub fn solve_task2(file_content: &str) -> Result<usize, ParseError> {
+    Ok(pour_sand(file_content, true)?.1)
 }
 
-pub fn solve_task2(file_content: &str) -> impl std::fmt::Display {
-    const SOURCE: (i32, i32) = (500, 0);
-    let (map, y_range) = parse_map(file_content);
+/// Pours the sand until it falls into the abyss or, if there is the floor, until it blocks
+/// the source. Returns the map with the sand and the number of the units at rest.
+fn pour_sand(file_content: &str, floor: bool) -> Result<(Map, usize), ParseError> {
+    let (map, y_range) = parse_map(file_content)?;
     let map_ref_cell = RefCell::new(map);
-    let mut i: usize = 0;
     let can_move = |x, y| {
-        if y >= y_range.end() + 2 {
+        if floor && y >= y_range.end() + 2 {
             return false;
         }
-        !map_ref_cell.borrow().contains_key(&(x, y))
+        map_ref_cell.borrow().is_free(x, y)
     };
-    let should_stop = |_, _| false;
-    loop {
-        i += 1;
-        match find_rest_sand_position(SOURCE, can_move, should_stop) {
-            None => unreachable!(),
-            Some((x, y)) => {
-                if (x, y) == SOURCE {
-                    return i;
-                }
-                map_ref_cell.borrow_mut().insert((x, y), Unit::Sand);
-            }
+    let should_stop = |_, y| !floor && y > *y_range.end();
+    let mut rested = 0;
+    while let Some((x, y)) = find_rest_sand_position(SOURCE, can_move, should_stop) {
+        map_ref_cell.borrow_mut().insert(x, y, Unit::Sand);
+        rested += 1;
+        if (x, y) == SOURCE {
+            break;
         }
     }
+    Ok((map_ref_cell.into_inner(), rested))
+}
+
+/// Draws the cave with the sand piled up to the source on the floor.
+pub fn draw(file_content: &str) -> Result<Image, ParseError> {
+    let (map, _) = pour_sand(file_content, true)?;
+    let image = Image::from_grid(&map.grid, |unit| match unit {
+        None => 0,
+        Some(Unit::Wall) => 1,
+        Some(Unit::Sand) => 2,
+    });
+    Ok(image.with_palette(PALETTE.to_vec()))
 }
 
+const SOURCE: (i32, i32) = (500, 0);
+
+/// Colors of the air, the walls, the sand at rest and the falling sand.
+const PALETTE: [Rgb; 4] = [
+    [0x1e, 0x1e, 0x28],
+    [0x8c, 0x8c, 0x8c],
+    [0xe6, 0xc2, 0x8c],
+    [0xff, 0xe8, 0xb0],
+];
+
 fn find_rest_sand_position(
     source: (i32, i32),
     can_move: impl Fn(i32, i32) -> bool,
     should_stop: impl Fn(i32, i32) -> bool,
 ) -> Option<(i32, i32)> {
     let (mut x, mut y) = source;
-    loop {
-        let prev_y = y;
-        for (next_x, next_y) in [(x, y + 1), (x - 1, y + 1), (x + 1, y + 1)] {
-            if !can_move(next_x, next_y) {
-                continue;
-            }
+    while let Some((next_x, next_y)) = fall(x, y, &can_move) {
+        (x, y) = (next_x, next_y);
+        if should_stop(x, y) {
+            return None;
+        }
+    }
+    Some((x, y))
+}
 
-            x = next_x;
-            y = next_y;
+/// Returns where the unit of sand falls next, or `None` if it comes to rest.
+fn fall(x: i32, y: i32, can_move: impl Fn(i32, i32) -> bool) -> Option<(i32, i32)> {
+    [(x, y + 1), (x - 1, y + 1), (x + 1, y + 1)]
+        .into_iter()
+        .find(|&(next_x, next_y)| can_move(next_x, next_y))
+}
 
-            if should_stop(x, y) {
-                return None;
-            }
-            break;
+/// The sand of the second part falling one unit and one move at a time.
+pub struct SandFrames {
+    map: Map,
+    floor: i32,
+    /// Position of the falling unit, if there is one.
+    sand: Option<(i32, i32)>,
+}
+
+impl SandFrames {
+    pub fn new(file_content: &str) -> Result<Self, ParseError> {
+        let (map, y_range) = parse_map(file_content)?;
+        Ok(Self {
+            map,
+            floor: y_range.end() + 2,
+            sand: None,
+        })
+    }
+}
+
+impl Frames for SandFrames {
+    fn frame(&self) -> Image {
+        let mut image = Image::from_grid(&self.map.grid, |unit| match unit {
+            None => 0,
+            Some(Unit::Wall) => 1,
+            Some(Unit::Sand) => 2,
+        })
+        .with_palette(PALETTE.to_vec());
+        if let Some(pos) = self.sand.and_then(|(x, y)| self.map.pos(x, y)) {
+            image.pixels_mut()[pos] = 3;
         }
-        if y == prev_y {
-            return Some((x, y));
+        image
+    }
+
+    fn step(&mut self) -> bool {
+        let Some((x, y)) = self.sand else {
+            if !self.map.is_free(SOURCE.0, SOURCE.1) {
+                return false;
+            }
+            self.sand = Some(SOURCE);
+            return true;
+        };
+        let can_move = |x, y| y < self.floor && self.map.is_free(x, y);
+        match fall(x, y, can_move) {
+            Some(next) => self.sand = Some(next),
+            None => {
+                self.map.insert(x, y, Unit::Sand);
+                self.sand = None;
+            }
         }
+        true
     }
 }
 
-fn parse_map(file_content: &str) -> (BTreeMap<(i32, i32), Unit>, RangeInclusive<i32>) {
-    let wall_coordinates = parse(file_content).flat_map(|path| {
-        path.into_iter()
-            .tuple_windows::<(_, _)>()
-            .flat_map(|((x1, y1), (x2, y2))| {
-                if x1 == x2 {
-                    (y1.min(y2)..=y2.max(y1))
-                        .into_iter()
-                        .map(|y| (x1, y))
-                        .collect::<Vec<_>>()
-                } else {
-                    (x1.min(x2)..=x1.max(x2))
-                        .into_iter()
-                        .map(|x| (x, y1))
-                        .collect::<Vec<_>>()
-                }
-            })
-    });
+/// Part of the cave around the source, wide enough for the sand to pile up to it on the floor.
+struct Map {
+    grid: Grid<Option<Unit>>,
+    min_x: i32,
+}
 
-    let mut map: BTreeMap<(i32, i32), Unit> = BTreeMap::new();
-    let mut min_y = i32::MAX;
-    let mut max_y = i32::MIN;
-    for (x, y) in wall_coordinates {
-        map.insert((x, y), Unit::Wall);
-        min_y = min_y.min(y);
-        max_y = max_y.max(y);
+impl Map {
+    fn pos(&self, x: i32, y: i32) -> Option<(usize, usize)> {
+        Some((
+            usize::try_from(y).ok()?,
+            usize::try_from(x - self.min_x).ok()?,
+        ))
+    }
+
+    fn is_free(&self, x: i32, y: i32) -> bool {
+        self.pos(x, y)
+            .and_then(|pos| self.grid.get(pos))
+            .is_none_or(Option::is_none)
+    }
+
+    fn insert(&mut self, x: i32, y: i32, unit: Unit) {
+        let pos = self.pos(x, y).expect("the unit is above the floor");
+        self.grid[pos] = Some(unit);
     }
-    (map, min_y..=max_y)
 }
 
-fn parse(file_content: &str) -> impl Iterator<Item = Vec<(i32, i32)>> + '_ {
-    file_content.lines().map(|line| parse_path(line).unwrap().1)
+fn parse_map(file_content: &str) -> Result<(Map, RangeInclusive<i32>), ParseError> {
+    let paths = parse_lines(file_content, parse_path).collect::<Result<Vec<_>, _>>()?;
+    let wall_coordinates = paths
+        .into_iter()
+        .flat_map(|path| {
+            path.into_iter()
+                .tuple_windows::<(_, _)>()
+                .flat_map(|((x1, y1), (x2, y2))| {
+                    if x1 == x2 {
+                        (y1.min(y2)..=y2.max(y1))
+                            .map(|y| (x1, y))
+                            .collect::<Vec<_>>()
+                    } else {
+                        (x1.min(x2)..=x1.max(x2))
+                            .map(|x| (x, y1))
+                            .collect::<Vec<_>>()
+                    }
+                })
+        })
+        .collect::<Vec<_>>();
+
+    let (min_y, max_y) = wall_coordinates
+        .iter()
+        .map(|&(_, y)| y)
+        .minmax()
+        .into_option()
+        .unwrap_or((0, 0));
+    // the sand moves aside by one at most for every unit it falls, so the floor is reached
+    // no further than its depth from the source
+    let floor = max_y + 2;
+    let (min_x, max_x) = wall_coordinates
+        .iter()
+        .map(|&(x, _)| x)
+        .chain([SOURCE.0 - floor, SOURCE.0 + floor])
+        .minmax()
+        .into_option()
+        .unwrap();
+    let mut map = Map {
+        grid: Grid::new(floor as usize, (max_x - min_x + 1) as usize, None),
+        min_x,
+    };
+    for (x, y) in wall_coordinates {
+        map.insert(x, y, Unit::Wall);
+    }
+    Ok((map, min_y..=max_y))
 }
 
-fn parse_path(line: &str) -> IResult<&str, Vec<(i32, i32)>> {
+fn parse_path(line: &str) -> IResult<'_, Vec<(i32, i32)>> {
     nom::multi::separated_list1(
-        nom::bytes::complete::tag(" -> "),
+        tag(" -> "),
         nom::sequence::separated_pair(
             nom::character::complete::i32,
-            nom::bytes::complete::tag(","),
+            tag(","),
             nom::character::complete::i32,
         ),
     )(line)
 }
 
+pub struct Day;
+
+impl Solution for Day {
+    fn year(&self) -> u16 {
+        2022
+    }
+    fn day(&self) -> u8 {
+        14
+    }
+    fn title(&self) -> &'static str {
+        "Regolith Reservoir"
+    }
+    fn part1(&self, file_content: &str, _params: &Params) -> Result<Answer, ParseError> {
+        Ok(solve_task1(file_content)?.into())
+    }
+    fn part2(&self, file_content: &str, _params: &Params) -> Result<Answer, ParseError> {
+        Ok(solve_task2(file_content)?.into())
+    }
+    fn image(&self, file_content: &str, _params: &Params) -> Option<Result<Image, ParseError>> {
+        Some(draw(file_content))
+    }
+    fn frames(
+        &self,
+        file_content: &str,
+        _params: &Params,
+    ) -> Option<Result<Box<dyn Frames>, ParseError>> {
+        Some(SandFrames::new(file_content).map(|frames| Box::new(frames) as _))
+    }
+}
+
+pub fn register(registry: &mut Registry) {
+    registry.register(&Day);
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     const INPUT: &str = "498,4 -> 498,6 -> 496,6
 503,4 -> 502,4 -> 502,9 -> 494,9";
-    const ACTUAL: &str = include_str!("../benches/y22d14.txt");
     #[test]
     #[ignore]
     fn test_task1() {
-        assert_eq!(format!("{}", solve_task1(INPUT)), "24");
-    }
-
-    #[test]
-    #[ignore]
-    fn test_task1_actual() {
-        assert_eq!(format!("{}", solve_task1(ACTUAL)), "1406");
+        assert_eq!(format!("{}", solve_task1(INPUT).unwrap()), "24");
     }
 
     #[test]
     #[ignore]
     fn test_task2() {
-        assert_eq!(format!("{}", solve_task2(INPUT)), "93");
+        assert_eq!(format!("{}", solve_task2(INPUT).unwrap()), "93");
     }
 
     #[test]
     #[ignore]
-    fn test_task2_actual() {
-        assert_eq!(format!("{}", solve_task2(ACTUAL)), "20870");
+    fn test_frames() {
+        let mut frames = SandFrames::new(INPUT).unwrap();
+        while frames.step() {}
+        let image = frames.frame();
+        let sand = image
+            .pixels()
+            .iter()
+            .filter(|&(_, &index)| index == 2)
+            .count();
+        assert_eq!(sand, 93);
+        assert_eq!(image, draw(INPUT).unwrap());
     }
 }
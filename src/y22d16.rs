@@ -5,39 +5,55 @@ mod shortest;
 mod step;
 pub mod valve;
 
-pub fn solve_task1(file_content: &str, minutes: usize) -> usize {
-    part1::solve_task1(file_content, minutes)
+use crate::{
+    parsing::ParseError,
+    registry::Registry,
+    solution::{Answer, Cancel, Parameter, Params, Solution},
+};
+
+pub fn solve_task1(
+    file_content: &str,
+    minutes: usize,
+    cancel: &Cancel,
+) -> Result<usize, ParseError> {
+    part1::solve_task1(file_content, minutes, cancel)
 }
-pub fn solve_task2(file_content: &str) -> usize {
-    part2::solve_task2(file_content, 26)
+pub fn solve_task2(
+    file_content: &str,
+    minutes: usize,
+    cancel: &Cancel,
+) -> Result<usize, ParseError> {
+    part2::solve_task2(file_content, minutes, cancel)
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    const INPUT: &str = include_str!("./y22d16/example.txt");
-    const ACTUAL: &str = include_str!("../benches/y22d16.txt");
-    #[test]
-    #[ignore]
-    fn test_task1() {
-        assert_eq!(format!("{}", solve_task1(INPUT, 30)), "1651");
-    }
+const PARAMETERS: &[Parameter] = &[
+    Parameter::new::<usize>("minutes1", "30"),
+    Parameter::new::<usize>("minutes2", "26"),
+];
 
-    #[test]
-    #[ignore]
-    fn test_task1_actual() {
-        assert_eq!(format!("{}", solve_task1(ACTUAL, 30)), "1728");
-    }
+pub struct Day;
 
-    #[test]
-    #[ignore]
-    fn test_task2() {
-        assert_eq!(format!("{}", solve_task2(INPUT)), "1707");
+impl Solution for Day {
+    fn year(&self) -> u16 {
+        2022
     }
-
-    #[test]
-    #[ignore]
-    fn test_task2_actual() {
-        assert_eq!(format!("{}", solve_task2(ACTUAL)), "2304");
+    fn day(&self) -> u8 {
+        16
+    }
+    fn title(&self) -> &'static str {
+        "Proboscidea Volcanium"
     }
+    fn part1(&self, file_content: &str, params: &Params) -> Result<Answer, ParseError> {
+        Ok(solve_task1(file_content, params.get("minutes1"), params.cancel())?.into())
+    }
+    fn part2(&self, file_content: &str, params: &Params) -> Result<Answer, ParseError> {
+        Ok(solve_task2(file_content, params.get("minutes2"), params.cancel())?.into())
+    }
+    fn parameters(&self) -> &'static [Parameter] {
+        PARAMETERS
+    }
+}
+
+pub fn register(registry: &mut Registry) {
+    registry.register(&Day);
 }
@@ -1,53 +1,73 @@
+use std::collections::BTreeSet;
+
 use nom::{
     branch::alt,
-    bytes::complete::tag,
     character::{self, complete::alpha1},
-    combinator::map,
     multi::separated_list1,
     sequence::{preceded, tuple},
-    IResult,
 };
 
 use super::valve::Valve;
+use crate::parsing::{parse_lines, tag, Error, Expected, IResult, ParseError};
 
-pub fn parse(file_content: &str) -> Vec<Valve> {
-    file_content
-        .lines()
-        .flat_map(|line| parse_valve(line))
-        .map(|(_, b)| b)
-        .collect()
+/// Parses the valves, checking that the tunnels lead to the known ones and that
+/// there is the valve `AA` to start from.
+pub fn parse(file_content: &str) -> Result<Vec<Valve>, ParseError> {
+    let lines = parse_lines(file_content, parse_valve).collect::<Result<Vec<_>, _>>()?;
+    let names = lines
+        .iter()
+        .map(|(valve, _)| valve.name)
+        .collect::<BTreeSet<_>>();
+    if !names.contains(&parse_id("AA").unwrap().1) {
+        let end = &file_content[file_content.len()..];
+        return Err(ParseError::new(
+            file_content,
+            Error::new(end, Expected::Context("valve AA")),
+        ));
+    }
+    for (_, tunnels) in &lines {
+        if let Some(unknown) = tunnels
+            .iter()
+            .find(|tunnel| !names.contains(&parse_id(tunnel).unwrap().1))
+        {
+            return Err(ParseError::new(
+                file_content,
+                Error::new(unknown, Expected::Context("known valve")),
+            ));
+        }
+    }
+    Ok(lines.into_iter().map(|(valve, _)| valve).collect())
 }
 
-fn parse_valve(line: &str) -> IResult<&str, Valve> {
-    map(
-        tuple((parse_name, parse_rate, parse_paths)),
-        |(name, rate, paths)| Valve {
-            rate: rate,
-            paths: paths,
-            name: name,
-        },
-    )(line)
+/// Parses the valve together with the names of the valves its tunnels lead to.
+fn parse_valve(line: &str) -> IResult<'_, (Valve, Vec<&str>)> {
+    let (input, (name, rate, tunnels)) = tuple((parse_name, parse_rate, parse_paths))(line)?;
+    let paths = tunnels
+        .iter()
+        .map(|tunnel| parse_id(tunnel).unwrap().1)
+        .collect();
+    Ok((input, (Valve { rate, paths, name }, tunnels)))
 }
 
-fn parse_name(line: &str) -> IResult<&str, usize> {
+fn parse_name(line: &str) -> IResult<'_, usize> {
     preceded(tag("Valve "), parse_id)(line)
 }
 
-pub fn parse_id(line: &str) -> IResult<&str, usize> {
+pub fn parse_id(line: &str) -> IResult<'_, usize> {
     let (input, letters) = alpha1(line)?;
     let mut res: usize = 0;
     for ch in letters.as_bytes().iter() {
-        res = res << 8;
+        res <<= 8;
         res |= (*ch) as usize;
     }
     Ok((input, res))
 }
 
-fn parse_rate(input: &str) -> IResult<&str, u16> {
+fn parse_rate(input: &str) -> IResult<'_, u16> {
     preceded(tag(" has flow rate="), character::complete::u16)(input)
 }
-fn parse_paths(input: &str) -> IResult<&str, Vec<usize>> {
-    let parse_list = separated_list1(tag(", "), parse_id);
+fn parse_paths(input: &str) -> IResult<'_, Vec<&str>> {
+    let parse_list = separated_list1(tag(", "), alpha1);
     preceded(
         alt((
             tag("; tunnel leads to valve "),
@@ -56,3 +76,29 @@ fn parse_paths(input: &str) -> IResult<&str, Vec<usize>> {
         parse_list,
     )(input)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unknown_valves() {
+        let content = "Valve AA has flow rate=0; tunnels lead to valves BB, CC\nValve BB has flow rate=13; tunnel leads to valve AA";
+        assert_eq!(
+            parse(content).unwrap_err().to_string(),
+            "line 1, column 54: expected known valve, found \"CC\""
+        );
+        assert_eq!(
+            parse("Valve BB has flow rate=13; tunnel leads to valve BB")
+                .unwrap_err()
+                .to_string(),
+            "line 1, column 52: expected valve AA, found end of line"
+        );
+        assert_eq!(
+            parse("Valve AA has flow rate=x; tunnel leads to valve AA")
+                .unwrap_err()
+                .to_string(),
+            "line 1, column 24: expected number, found \"x; tunnel leads to v\""
+        );
+    }
+}
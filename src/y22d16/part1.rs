@@ -6,6 +6,7 @@ use super::{
     step::Step,
     valve::{self, Valve},
 };
+use crate::{parsing::ParseError, solution::Cancel};
 
 #[derive(Debug, Clone)]
 struct FullState {
@@ -45,8 +46,12 @@ impl FullState {
     }
 }
 
-pub fn solve_task1(file_content: &str, minutes: usize) -> usize {
-    let valves = parse::parse(file_content);
+pub fn solve_task1(
+    file_content: &str,
+    minutes: usize,
+    cancel: &Cancel,
+) -> Result<usize, ParseError> {
+    let valves = parse::parse(file_content)?;
     let valves_map = valves
         .into_iter()
         .map(|valve| (valve.name, valve))
@@ -64,6 +69,7 @@ pub fn solve_task1(file_content: &str, minutes: usize) -> usize {
 
     let mut max_pressure_collected = 0;
     while let Some(mut task) = tasks.pop() {
+        cancel.check();
         let mut has_plans = false;
         for possible_plan in get_possible_plans(&valves_map, &shortest_paths, &task) {
             has_plans = true;
@@ -82,7 +88,7 @@ pub fn solve_task1(file_content: &str, minutes: usize) -> usize {
             tasks.sort_by_key(|x| x.approximate_quality());
         }
     }
-    max_pressure_collected
+    Ok(max_pressure_collected)
 }
 
 fn get_possible_plans(
@@ -90,7 +96,7 @@ fn get_possible_plans(
     shortest_paths: &BTreeMap<(usize, usize), Vec<usize>>,
     state: &FullState,
 ) -> Vec<Vec<Step>> {
-    if state.remaining_minutes <= 0 {
+    if state.remaining_minutes == 0 {
         return Vec::new();
     }
 
@@ -105,7 +111,7 @@ fn get_possible_plans(
         })
         .filter(|path| path.len() < state.remaining_minutes)
         .map(|p| {
-            p.into_iter()
+            p.iter()
                 .map(|valve| Step::GoTo(*valve))
                 .chain(std::iter::once(Step::Open))
                 .collect()
@@ -5,6 +5,7 @@ use super::{
     shortest::precalculate_shortest_paths,
     valve::Valve,
 };
+use crate::{parsing::ParseError, solution::Cancel};
 
 #[derive(Debug, Clone, Copy)]
 enum Goal {
@@ -64,7 +65,7 @@ impl Node {
         self.move_elephant(valves, shortest_paths);
         self.remaining_minutes -= 1;
     }
-    fn open_valve<'a>(&mut self, valves: &'a VS, valve: usize) {
+    fn open_valve(&mut self, valves: &VS, valve: usize) {
         self.open.insert(valve);
         self.flow += valves.get(&valve).unwrap().rate as usize;
     }
@@ -164,7 +165,7 @@ impl Node {
         valves: &'a BTreeMap<usize, Valve>,
         shortest_paths: &'a BTreeMap<(usize, usize), Vec<usize>>,
         place: usize,
-    ) -> impl Iterator<Item = usize> + '_ {
+    ) -> impl Iterator<Item = usize> + 'a {
         valves
             .iter()
             .filter(|(_, v)| v.rate > 0)
@@ -215,8 +216,12 @@ impl Node {
     }
 }
 
-pub(crate) fn solve_task2(file_content: &str, minutes: usize) -> usize {
-    let valves = parse::parse(file_content)
+pub(crate) fn solve_task2(
+    file_content: &str,
+    minutes: usize,
+    cancel: &Cancel,
+) -> Result<usize, ParseError> {
+    let valves = parse::parse(file_content)?
         .into_iter()
         .map(|valve| (valve.name, valve))
         .collect::<BTreeMap<_, _>>();
@@ -231,6 +236,7 @@ pub(crate) fn solve_task2(file_content: &str, minutes: usize) -> usize {
     let initial_id = parse_id("AA").unwrap().1;
     nodes.push(Node::new(minutes, initial_id, initial_id));
     while let Some(mut node) = nodes.pop() {
+        cancel.check();
         while node.has_plan_and_time() {
             node.do_move(&valves, &shortest_paths);
         }
@@ -241,7 +247,7 @@ pub(crate) fn solve_task2(file_content: &str, minutes: usize) -> usize {
                     next_node.elephant,
                     next_node.remaining_minutes,
                 );
-                let best_flow = best.get(&p).map(|e| *e).unwrap_or_default();
+                let best_flow = best.get(&p).copied().unwrap_or_default();
                 if best_flow > next_node.flow {
                     continue;
                 } else {
@@ -255,5 +261,5 @@ pub(crate) fn solve_task2(file_content: &str, minutes: usize) -> usize {
             }
         }
     }
-    max_pressure_collected
+    Ok(max_pressure_collected)
 }
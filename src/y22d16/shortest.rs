@@ -1,86 +1,48 @@
-use std::collections::{BTreeMap, BTreeSet, BinaryHeap};
+use std::collections::{BTreeMap, BTreeSet};
 
 use super::{parse::parse_id, valve::Valve};
-
-pub fn precalculate_shortest_paths<'a>(
+use crate::search::bfs;
+
+/// Returns the shortest paths between the valves reachable from `AA`, a path lists the valves
+/// after the first one.
+///
+/// The paths to a valve follow a single BFS tree grown from it against the tunnels, so the
+/// equally short paths to it merge as soon as they meet and part 2 prunes more of its states.
+pub fn precalculate_shortest_paths(
     valves_map: &BTreeMap<usize, Valve>,
 ) -> BTreeMap<(usize, usize), Vec<usize>> {
     let reachable = get_reachable_valves(valves_map, parse_id("AA").unwrap().1);
 
-    let mut memory: BTreeMap<(usize, usize), Option<Vec<usize>>> = BTreeMap::new();
-
-    for &from in valves_map.keys().filter(|k| reachable.contains(*k)) {
-        let mut tasks: BinaryHeap<ShortestPath> = BinaryHeap::new();
-        tasks.push(Vec::new().into());
-        while let Some(ShortestPath(path)) = tasks.pop() {
-            let current = *path.iter().last().unwrap_or(&from);
-            if !memory.contains_key(&(from, current)) && from != current {
-                memory.insert((from, current), Some(path.clone()));
-            }
-            for &neighbour in &valves_map.get(&current).unwrap().paths {
-                if path.contains(&neighbour) {
-                    continue;
-                }
-                if memory.contains_key(&(from, neighbour)) {
-                    continue;
-                }
-                let mut new_task = path.clone();
-                new_task.push(neighbour);
-                tasks.push(new_task.into());
-            }
+    let mut tunnels_to: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+    for (&from, valve) in valves_map {
+        for &to in &valve.paths {
+            tunnels_to.entry(to).or_default().push(from);
         }
     }
 
-    memory
-        .into_iter()
-        .flat_map(|(k, mp)| mp.map(|p| (k, p)))
-        .collect()
-}
-
-#[derive(Debug)]
-struct ShortestPath(Vec<usize>);
-
-impl PartialEq for ShortestPath {
-    fn eq(&self, other: &Self) -> bool {
-        self.0.len() == other.0.len()
-    }
-}
-impl Eq for ShortestPath {}
-
-impl PartialOrd for ShortestPath {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(self.cmp(&other))
-    }
-}
-impl Ord for ShortestPath {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        let n = self.0.len();
-        let m = other.0.len();
-        if n > m {
-            std::cmp::Ordering::Less
-        } else if n < m {
-            std::cmp::Ordering::Greater
-        } else {
-            std::cmp::Ordering::Equal
+    let mut memory = BTreeMap::new();
+    for &to in &reachable {
+        let tree = bfs([to], |valve: &usize| {
+            tunnels_to.get(valve).into_iter().flatten().copied()
+        })
+        .with_paths()
+        .into_tree();
+        for (from, _) in tree
+            .costs()
+            .filter(|&(from, _)| from != to && reachable.contains(&from))
+        {
+            let mut path = tree.path(&from).unwrap();
+            path.reverse();
+            memory.insert((from, to), path[1..].to_vec());
         }
     }
-}
-impl From<Vec<usize>> for ShortestPath {
-    fn from(v: Vec<usize>) -> Self {
-        ShortestPath(v)
-    }
+    memory
 }
 
 fn get_reachable_valves(valves_map: &BTreeMap<usize, Valve>, from: usize) -> BTreeSet<usize> {
-    let mut visited = BTreeSet::new();
-    let mut tasks = vec![from];
-    while let Some(valve) = tasks.pop() {
-        visited.insert(valve);
-        for neighbour in &valves_map.get(&valve).unwrap().paths {
-            if !visited.contains(neighbour) {
-                tasks.push(*neighbour)
-            }
-        }
-    }
-    visited
+    bfs([from], |valve: &usize| {
+        valves_map[valve].paths.iter().copied()
+    })
+    .map(|(valve, _)| valve)
+    .collect()
 }
@@ -2,18 +2,30 @@ mod chamber;
 mod direction;
 mod figure;
 mod get_figures;
-mod infinite;
 mod parse;
-mod vector;
+mod rockfall;
+mod tower;
 
+use crate::{
+    animation::Frames,
+    cycle::{self, Cycle},
+    grid::Grid,
+    parsing::ParseError,
+    point::Point2,
+    registry::Registry,
+    render::Image,
+    solution::{Answer, Parameter, Params, Solution},
+    trace::Trace,
+};
 use get_figures::get_figures;
-use infinite::infinite;
+use std::{ops::RangeInclusive, str::FromStr};
 
 use self::{
     chamber::Chamber,
     direction::Direction::{self, *},
     figure::Figure,
-    vector::Vector,
+    rockfall::Rockfall,
+    tower::Tower,
 };
 
 struct FallingFigure<'i, Dirs>
@@ -23,7 +35,7 @@ where
     chamber: &'i Chamber,
     figure: &'i Figure,
     direction: &'i mut Dirs,
-    position: Vector,
+    position: Point2<isize>,
     finished: bool,
 }
 
@@ -33,104 +45,192 @@ impl<'i, Dirs: Iterator<Item = Direction>> FallingFigure<'i, Dirs> {
             chamber,
             figure,
             direction: dirs,
-            position: Vector::new(2, (chamber.height() + 3) as isize),
+            position: Point2::new(2, (chamber.height() + 3) as isize),
             finished: false,
         }
     }
 }
 
 impl<'i, Dirs: Iterator<Item = Direction>> Iterator for FallingFigure<'i, Dirs> {
-    type Item = (&'i Figure, Vector);
+    type Item = (&'i Figure, Point2<isize>);
 
     fn next(&mut self) -> Option<Self::Item> {
         if self.finished {
             return None;
         }
-        // self.chamber.print(Some((self.figure, self.position)));
-        match self.direction.next() {
-            None => None,
-            Some(dir) => {
-                let step = match dir {
-                    Left => Vector::new(-1, 0),
-                    Right => Vector::new(1, 0),
-                    Down => Vector::new(0, -1),
-                };
-                let new_pos = self.position.plus(&step);
-
-                let can_move = new_pos.y >= 0
-                    && new_pos.x >= 0
-                    && new_pos.x + self.figure.width() as isize <= self.chamber.width() as isize
-                    && self
-                        .figure
-                        .points
-                        .iter()
-                        .all(|p| !self.chamber.is_taken(&p.plus(&new_pos)));
-
-                if can_move {
-                    self.position = new_pos;
-                } else if dir.is_down() {
-                    self.finished = true;
-                    return None;
-                }
-                Some((self.figure, self.position))
+        let dir = self.direction.next()?;
+        match shift(self.chamber, self.figure, self.position, dir) {
+            Some(position) => self.position = position,
+            None if dir.is_down() => {
+                self.finished = true;
+                return None;
             }
+            None => {}
         }
+        Some((self.figure, self.position))
     }
 }
 
-pub fn solve_task1<const W: usize>(file_content: &str, stop: usize) -> usize {
+/// Returns the position of the figure moved in the direction, or `None` if it hits the
+/// walls, the floor or the rocks.
+fn shift(
+    chamber: &Chamber,
+    figure: &Figure,
+    position: Point2<isize>,
+    dir: Direction,
+) -> Option<Point2<isize>> {
+    let step = match dir {
+        Left => Point2::new(-1, 0),
+        Right => Point2::new(1, 0),
+        Down => Point2::new(0, -1),
+    };
+    let new_pos = position + step;
+    let can_move = new_pos.y >= 0
+        && new_pos.x >= 0
+        && new_pos.x + figure.width() as isize <= chamber.width() as isize
+        && figure
+            .points
+            .iter()
+            .all(|p| !chamber.is_taken(*p + new_pos));
+    can_move.then_some(new_pos)
+}
+
+pub fn solve_task1(file_content: &str, width: usize, stop: usize) -> Result<usize, ParseError> {
+    tower_height(file_content, width, stop, &Trace::default())
+}
+pub fn solve_task2(
+    file_content: &str,
+    width: usize,
+    stop: usize,
+    trace: &Trace,
+) -> Result<usize, ParseError> {
+    tower_height(file_content, width, stop, trace)
+}
+
+/// Returns the height of the tower after the rocks fall, the simulation is fast-forwarded
+/// once the state of the tower repeats.
+fn tower_height(
+    file_content: &str,
+    width: usize,
+    rocks: usize,
+    trace: &Trace,
+) -> Result<usize, ParseError> {
     let figures = get_figures();
-    let dirs = parse::parse(file_content).collect::<Vec<_>>();
-    let dirs = infinite(&dirs);
-    let mut all_dirs = dirs.cloned().flat_map(|dir| [dir, Down]);
-    let mut chamber = Chamber::new(W);
-    let mut figures_it = infinite::infinite(&figures);
-    let mut heights = std::iter::repeat_with(move || {
-        let fig = figures_it.next().unwrap();
-        let pos = FallingFigure::new(&chamber, fig, &mut all_dirs)
-            .map(|p| p.1)
-            .last()
-            .unwrap();
-        chamber.place(fig, pos);
-        chamber.height()
-    });
-    heights.nth(stop - 1).unwrap()
+    let jets = parse::parse(file_content)?;
+    let history = cycle::simulate(
+        Tower::new(width, &figures, &jets),
+        Tower::drop_rock,
+        Tower::fingerprint,
+        Tower::height,
+        rocks,
+    );
+    if let Some(Cycle { start, length }) = history.cycle() {
+        trace.event("cycle", &[("start", &start), ("length", &length)]);
+    }
+    Ok(history.at(rocks))
 }
-pub fn solve_task2<const N: usize>(file_content: &str) -> impl std::fmt::Display {
-    const CYCLE: usize = 1725;
-    const ADDITION: usize = 2659;
-    let rem = N / CYCLE;
-    let q = N % CYCLE;
-
-    println!("rem = {rem}, q = {q}");
-    ADDITION * rem + solve_task1::<7>(file_content, q)
+
+/// Draws the chamber after the rocks fall, the top row first.
+pub fn draw(file_content: &str, width: usize, rocks: usize) -> Result<Image, ParseError> {
+    let figures = get_figures();
+    let jets = parse::parse(file_content)?;
+    let mut tower = Tower::new(width, &figures, &jets);
+    for _ in 0..rocks {
+        tower.drop_rock();
+    }
+    let chamber = tower.chamber();
+    let levels = chamber.top(chamber.height());
+    let mut pixels = Grid::new(levels.len(), width, 0);
+    for (row, level) in levels.iter().rev().enumerate() {
+        for col in 0..width {
+            pixels[(row, col)] = (level >> col) & 1;
+        }
+    }
+    Ok(Image::new(pixels))
 }
-#[cfg(test)]
-mod tests {
-    use super::*;
-    // COMMON: 35, addition: 53
-    const INPUT: &str = include_str!("./y22d17/example.txt");
-    // COMMON: 1725, addition: 2659
-    const ACTUAL: &str = include_str!("../benches/y22d17.txt");
-    #[test]
-    #[ignore]
-    fn test_task1() {
-        assert_eq!(format!("{}", solve_task1::<7>(INPUT, 2022)), "3068");
+
+/// The widths the chamber can have, its rows are bit masks of `u8` and the widest rock
+/// takes 4 columns.
+pub const WIDTHS: RangeInclusive<usize> = 4..=8;
+
+/// The width of the chamber parameter, the values outside of [`WIDTHS`] are rejected.
+struct Width(usize);
+
+impl FromStr for Width {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.parse() {
+            Ok(width) if WIDTHS.contains(&width) => Ok(Width(width)),
+            _ => Err(()),
+        }
     }
+}
 
-    #[test]
-    #[ignore]
-    fn test_task1_actual() {
-        assert_eq!(format!("{}", solve_task1::<7>(ACTUAL, 2022)), "3109");
+const PARAMETERS: &[Parameter] = &[
+    Parameter::new::<Width>("width", "7"),
+    Parameter::new::<usize>("rocks1", "2022"),
+    Parameter::new::<usize>("rocks2", "1000000000000"),
+];
+
+pub struct Day;
+
+impl Solution for Day {
+    fn year(&self) -> u16 {
+        2022
+    }
+    fn day(&self) -> u8 {
+        17
+    }
+    fn title(&self) -> &'static str {
+        "Pyroclastic Flow"
+    }
+    fn part1(&self, file_content: &str, params: &Params) -> Result<Answer, ParseError> {
+        let width = params.get::<Width>("width").0;
+        Ok(solve_task1(file_content, width, params.get("rocks1"))?.into())
+    }
+    fn part2(&self, file_content: &str, params: &Params) -> Result<Answer, ParseError> {
+        let width = params.get::<Width>("width").0;
+        Ok(solve_task2(file_content, width, params.get("rocks2"), params.trace())?.into())
+    }
+    fn parameters(&self) -> &'static [Parameter] {
+        PARAMETERS
+    }
+    fn image(&self, file_content: &str, params: &Params) -> Option<Result<Image, ParseError>> {
+        Some(draw(
+            file_content,
+            params.get::<Width>("width").0,
+            params.get("rocks1"),
+        ))
     }
+    fn frames(
+        &self,
+        file_content: &str,
+        params: &Params,
+    ) -> Option<Result<Box<dyn Frames>, ParseError>> {
+        let frames = Rockfall::new(
+            file_content,
+            params.get::<Width>("width").0,
+            params.get("rocks1"),
+        );
+        Some(frames.map(|frames| Box::new(frames) as _))
+    }
+}
+
+pub fn register(registry: &mut Registry) {
+    registry.register(&Day);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
     #[test]
-    #[ignore]
-    fn test_task2_actual() {
-        assert_eq!(format!("{}", solve_task2::<2022>(ACTUAL)), "3109");
-        assert_eq!(
-            format!("{}", solve_task2::<1_000_000_000_000>(ACTUAL)),
-            "1541449275365"
-        );
+    fn test_widths() {
+        let mut params = Params::new(PARAMETERS);
+        assert!(params.set("width", "3").is_err());
+        assert!(params.set("width", "9").is_err());
+        params.set("width", "8").unwrap();
+        assert_eq!(params.get::<Width>("width").0, 8);
     }
 }
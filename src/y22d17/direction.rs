@@ -20,16 +20,3 @@ impl std::fmt::Debug for Direction {
         }
     }
 }
-
-impl TryFrom<char> for Direction {
-    type Error = String;
-
-    fn try_from(value: char) -> Result<Self, Self::Error> {
-        match value {
-            '<' => Ok(Direction::Left),
-            '>' => Ok(Direction::Right),
-            'v' => Ok(Direction::Down),
-            _ => Err("invalid direction char".to_owned()),
-        }
-    }
-}
@@ -1,9 +1,10 @@
 use itertools::Itertools;
 
-use super::vector::Vector;
+use crate::point::Point2;
 
+#[derive(Default)]
 pub struct Figure {
-    pub points: Vec<Vector>,
+    pub points: Vec<Point2<isize>>,
 }
 
 impl Figure {
@@ -29,7 +30,7 @@ impl Figure {
         self.points
             .iter()
             .take_while(|v| v.y <= target_y)
-            .contains(&Vector::new(target_x, target_y))
+            .contains(&Point2::new(target_x, target_y))
     }
 }
 
@@ -52,14 +53,7 @@ impl std::fmt::Debug for Figure {
 impl From<Vec<(isize, isize)>> for Figure {
     fn from(points: Vec<(isize, isize)>) -> Self {
         Figure {
-            points: points.into_iter().map(Vector::from).collect(),
-        }
-    }
-}
-impl Default for Figure {
-    fn default() -> Self {
-        Self {
-            points: Default::default(),
+            points: points.into_iter().map(Point2::from).collect(),
         }
     }
 }
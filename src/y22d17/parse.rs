@@ -1,5 +1,20 @@
 use super::direction::Direction;
+use crate::parsing::{parse_all, IResult, ParseError};
+use nom::{
+    branch::alt,
+    character::complete::{char, multispace0},
+    combinator::value,
+    multi::many1,
+    sequence::terminated,
+};
 
-pub fn parse(file: &str) -> impl Iterator<Item = Direction> + '_ {
-    file.chars().map(|ch| ch.try_into()).map(Result::unwrap)
+fn parse_direction(input: &str) -> IResult<'_, Direction> {
+    alt((
+        value(Direction::Left, char('<')),
+        value(Direction::Right, char('>')),
+    ))(input)
+}
+
+pub fn parse(file: &str) -> Result<Vec<Direction>, ParseError> {
+    parse_all(file, file, terminated(many1(parse_direction), multispace0))
 }
@@ -0,0 +1,108 @@
+use super::{
+    chamber::Chamber,
+    direction::Direction::{self, Down},
+    figure::Figure,
+    get_figures::get_figures,
+    parse, shift,
+};
+use crate::{animation::Frames, grid::Grid, parsing::ParseError, point::Point2, render::Image};
+
+/// Rows of the chamber shown below the top of the falling rock.
+const VIEW_ROWS: usize = 40;
+
+/// The rocks falling one move at a time: a push of the jet, then a fall by one unit.
+pub struct Rockfall {
+    chamber: Chamber,
+    figures: Vec<Figure>,
+    jets: Vec<Direction>,
+    rock: usize,
+    jet: usize,
+    /// Rocks that have not started falling yet.
+    rocks: usize,
+    /// Position of the falling rock, if there is one.
+    falling: Option<Point2<isize>>,
+    /// Whether the jet has pushed the falling rock since its last fall.
+    pushed: bool,
+}
+
+impl Rockfall {
+    pub fn new(file_content: &str, width: usize, rocks: usize) -> Result<Self, ParseError> {
+        Ok(Self {
+            chamber: Chamber::new(width),
+            figures: get_figures(),
+            jets: parse::parse(file_content)?,
+            rock: 0,
+            jet: 0,
+            rocks,
+            falling: None,
+            pushed: false,
+        })
+    }
+}
+
+impl Frames for Rockfall {
+    fn frame(&self) -> Image {
+        let figure = &self.figures[self.rock];
+        let falling = self.falling.map(|pos| {
+            let points = figure.points.iter().map(|&p| p + pos).collect::<Vec<_>>();
+            (points, pos.y as usize + figure.height())
+        });
+        let top = falling
+            .as_ref()
+            .map_or(0, |&(_, top)| top)
+            .max(self.chamber.height());
+        let rows = top.min(VIEW_ROWS);
+        let width = self.chamber.width();
+        let mut pixels = Grid::new(rows, width, 0);
+        for row in 0..rows {
+            let y = (top - 1 - row) as isize;
+            for x in 0..width {
+                let point = Point2::new(x as isize, y);
+                if self.chamber.is_taken(point) {
+                    pixels[(row, x)] = 1;
+                } else if falling
+                    .as_ref()
+                    .is_some_and(|(points, _)| points.contains(&point))
+                {
+                    pixels[(row, x)] = 2;
+                }
+            }
+        }
+        Image::new(pixels).with_palette(vec![
+            [0x14, 0x10, 0x18],
+            [0x80, 0x78, 0x70],
+            [0xff, 0x80, 0x30],
+        ])
+    }
+
+    fn step(&mut self) -> bool {
+        let Some(pos) = self.falling else {
+            if self.rocks == 0 {
+                return false;
+            }
+            self.rocks -= 1;
+            self.falling = Some(Point2::new(2, (self.chamber.height() + 3) as isize));
+            self.pushed = false;
+            return true;
+        };
+        let dir = if self.pushed {
+            Down
+        } else {
+            let dir = self.jets[self.jet];
+            self.jet = (self.jet + 1) % self.jets.len();
+            dir
+        };
+        self.pushed = !self.pushed;
+        let figure = &self.figures[self.rock];
+        match shift(&self.chamber, figure, pos, dir) {
+            Some(pos) => self.falling = Some(pos),
+            None if dir.is_down() => {
+                self.chamber.place(figure, pos);
+                self.rock = (self.rock + 1) % self.figures.len();
+                self.falling = None;
+            }
+            None => {}
+        }
+        true
+    }
+}
@@ -0,0 +1,63 @@
+use super::{
+    chamber::Chamber,
+    direction::Direction::{self, Down},
+    figure::Figure,
+    FallingFigure,
+};
+
+/// Rows at the top of the chamber that tell the states apart, the rocks are not expected to
+/// fall deeper than that.
+const SURFACE_ROWS: usize = 64;
+
+/// State of the chamber between the falling rocks.
+#[derive(Debug, Clone)]
+pub struct Tower<'a> {
+    chamber: Chamber,
+    figures: &'a [Figure],
+    jets: &'a [Direction],
+    rock: usize,
+    jet: usize,
+}
+
+impl<'a> Tower<'a> {
+    pub fn new(width: usize, figures: &'a [Figure], jets: &'a [Direction]) -> Self {
+        Self {
+            chamber: Chamber::new(width),
+            figures,
+            jets,
+            rock: 0,
+            jet: 0,
+        }
+    }
+
+    pub fn chamber(&self) -> &Chamber {
+        &self.chamber
+    }
+
+    pub fn height(&self) -> usize {
+        self.chamber.height()
+    }
+
+    /// Lets the next rock fall until it rests.
+    pub fn drop_rock(&mut self) {
+        let figure = &self.figures[self.rock];
+        let (jets, jet) = (self.jets, &mut self.jet);
+        let mut dirs = std::iter::from_fn(|| {
+            let dir = jets[*jet];
+            *jet = (*jet + 1) % jets.len();
+            Some(dir)
+        })
+        .flat_map(|dir| [dir, Down]);
+        let pos = FallingFigure::new(&self.chamber, figure, &mut dirs)
+            .map(|p| p.1)
+            .last()
+            .unwrap();
+        self.chamber.place(figure, pos);
+        self.rock = (self.rock + 1) % self.figures.len();
+    }
+
+    /// Identifies the state by the next rock, the next jet and the top of the chamber.
+    pub fn fingerprint(&self) -> (usize, usize, Vec<u8>) {
+        (self.rock, self.jet, self.chamber.top(SURFACE_ROWS).to_vec())
+    }
+}
@@ -1,24 +1,24 @@
-use std::collections::{BTreeMap, BTreeSet};
-
-use itertools::Itertools;
-
-pub fn get_neighbours(x: i32, y: i32, z: i32) -> Vec<(i32, i32, i32)> {
-    vec![
-        (x - 1, y, z),
-        (x + 1, y, z),
-        (x, y - 1, z),
-        (x, y + 1, z),
-        (x, y, z - 1),
-        (x, y, z + 1),
-    ]
-}
-
-pub fn find_exterior_area(dots: impl Iterator<Item = (i32, i32, i32)>) -> usize {
-    let mut d: BTreeMap<(i32, i32, i32), u8> = Default::default();
-    for (x, y, z) in dots {
+use crate::{
+    parsing::{parse_lines, tag, IResult, ParseError},
+    point::Point3,
+    registry::Registry,
+    search::bfs,
+    solution::{Answer, Params, Solution},
+};
+use nom::{
+    character::complete,
+    sequence::{preceded, tuple},
+};
+use std::collections::{BTreeMap, HashSet};
+
+type Dot = Point3<i32>;
+
+pub fn find_exterior_area(dots: impl Iterator<Item = Dot>) -> usize {
+    let mut d: BTreeMap<Dot, u8> = Default::default();
+    for dot in dots {
         let mut open_edges = 6;
 
-        for neighbour in get_neighbours(x, y, z) {
+        for neighbour in dot.neighbours6() {
             if !d.contains_key(&neighbour) {
                 continue;
             }
@@ -26,171 +26,78 @@ pub fn find_exterior_area(dots: impl Iterator<Item = (i32, i32, i32)>) -> usize
             *d.entry(neighbour).or_insert(6) -= 1;
         }
 
-        d.insert((x, y, z), open_edges);
+        d.insert(dot, open_edges);
     }
     d.values().map(|x| *x as usize).sum()
 }
 
-fn find_internal_dots(dots: &[(i32, i32, i32)]) -> Vec<(i32, i32, i32)> {
-    let min_x = dots.iter().map(|p| p.0).min().unwrap_or(i32::MAX);
-    let max_x = dots.iter().map(|p| p.0).max().unwrap_or(i32::MIN);
-    let min_y = dots.iter().map(|p| p.1).min().unwrap_or(i32::MAX);
-    let max_y = dots.iter().map(|p| p.1).max().unwrap_or(i32::MIN);
-    let min_z = dots.iter().map(|p| p.2).min().unwrap_or(i32::MAX);
-    let max_z = dots.iter().map(|p| p.2).max().unwrap_or(i32::MIN);
+/// Counts the sides of the dots reached by the steam, which spreads through the air around
+/// the droplet inside of its bounding box grown by one in every direction.
+fn find_outside_area(dots: &[Dot]) -> usize {
+    let lava = dots.iter().copied().collect::<HashSet<_>>();
+    let (Some(min), Some(max)) = (
+        dots.iter()
+            .copied()
+            .reduce(|a, b| Point3::new(a.x.min(b.x), a.y.min(b.y), a.z.min(b.z))),
+        dots.iter()
+            .copied()
+            .reduce(|a, b| Point3::new(a.x.max(b.x), a.y.max(b.y), a.z.max(b.z))),
+    ) else {
+        return 0;
+    };
+    let (min, max) = (min - Point3::new(1, 1, 1), max + Point3::new(1, 1, 1));
+    let is_air = |dot: &Dot| {
+        (min.x..=max.x).contains(&dot.x)
+            && (min.y..=max.y).contains(&dot.y)
+            && (min.z..=max.z).contains(&dot.z)
+            && !lava.contains(dot)
+    };
+    let steam = |dot: &Dot| dot.neighbours6().filter(is_air);
+    bfs([min], steam)
+        .map(|(dot, _)| dot.neighbours6().filter(|n| lava.contains(n)).count())
+        .sum()
+}
 
-    let mut grid = vec![
-        vec![vec![0; (max_x - min_x + 1) as usize]; (max_y - min_y + 1) as usize];
-        (max_z - min_z + 1) as usize
-    ];
+pub fn solve_task1(file_content: &str) -> Result<usize, ParseError> {
+    Ok(find_exterior_area(parse(file_content)?.into_iter()))
+}
+pub fn solve_task2(file_content: &str) -> Result<usize, ParseError> {
+    Ok(find_outside_area(&parse(file_content)?))
+}
 
-    for dot in dots {
-        grid[(dot.2 - min_z) as usize][(dot.1 - min_y) as usize][(dot.0 - min_x) as usize] =
-            u32::MAX;
-    }
+fn parse_dot(line: &str) -> IResult<'_, Dot> {
+    let (input, (x, y, z)) = tuple((
+        complete::i32,
+        preceded(tag(","), complete::i32),
+        preceded(tag(","), complete::i32),
+    ))(line)?;
+    Ok((input, Point3::new(x, y, z)))
+}
 
-    let mut color = 1;
-    loop {
-        let mut initial = None;
-        for z in min_z..=max_z {
-            if initial.is_some() {
-                break;
-            }
-            for y in min_y..=max_y {
-                if initial.is_some() {
-                    break;
-                }
-                for x in min_x..=max_x {
-                    if initial.is_some() {
-                        break;
-                    }
-                    let x_ind = (x - min_x) as usize;
-                    let y_ind = (y - min_y) as usize;
-                    let z_ind = (z - min_z) as usize;
-                    let v = grid[z_ind][y_ind][x_ind];
-                    if v == 0 {
-                        initial = Some((x, y, z));
-                    }
-                }
-            }
-        }
-        if initial.is_none() {
-            break;
-        }
-        let initial = initial.unwrap();
-        let mut visited = BTreeSet::new();
-        let mut tasks = vec![initial];
-        while let Some((x, y, z)) = tasks.pop() {
-            grid[(z - min_z) as usize][(y - min_y) as usize][(x - min_x) as usize] = color;
-            visited.insert((x, y, z));
-            for neighbour in get_neighbours(x, y, z) {
-                if visited.contains(&neighbour) {
-                    continue;
-                }
-                if neighbour.0 < min_x {
-                    continue;
-                }
-                if neighbour.1 < min_y {
-                    continue;
-                }
-                if neighbour.2 < min_z {
-                    continue;
-                }
-                let x_ind = (neighbour.0 - min_x) as usize;
-                let y_ind = (neighbour.1 - min_y) as usize;
-                let z_ind = (neighbour.2 - min_z) as usize;
-                let neighbour_v = grid
-                    .get(z_ind)
-                    .and_then(|g| g.get(y_ind).and_then(|g| g.get(x_ind)));
-                if neighbour_v.is_none() {
-                    continue;
-                }
-                let neighbour_v = *neighbour_v.unwrap();
-                if neighbour_v == 0 {
-                    tasks.push(neighbour);
-                }
-            }
-        }
-        color += 1;
-    }
+fn parse(file_content: &str) -> Result<Vec<Dot>, ParseError> {
+    parse_lines(file_content, parse_dot).collect()
+}
 
-    let mut not_internal_colors = BTreeSet::new();
-    not_internal_colors.insert(u32::MAX);
-    for z in min_z..=max_z {
-        for y in min_y..=max_y {
-            for x in min_x..=max_x {
-                if x == min_x || x == max_x || y == min_y || y == max_y || z == max_z || z == min_z
-                {
-                    let value =
-                        grid[(z - min_z) as usize][(y - min_y) as usize][(x - min_x) as usize];
+pub struct Day;
 
-                    not_internal_colors.insert(value);
-                }
-            }
-        }
+impl Solution for Day {
+    fn year(&self) -> u16 {
+        2022
     }
-    let mut res = Vec::new();
-    for z in min_z..=max_z {
-        for y in min_y..=max_y {
-            for x in min_x..=max_x {
-                let value = grid[(z - min_z) as usize][(y - min_y) as usize][(x - min_x) as usize];
-                if not_internal_colors.contains(&value) {
-                    continue;
-                }
-                res.push((x, y, z));
-            }
-        }
+    fn day(&self) -> u8 {
+        18
     }
-    res
-}
-
-pub fn solve_task1(file_content: &str) -> usize {
-    find_exterior_area(parse(file_content))
-}
-pub fn solve_task2(file_content: &str) -> impl std::fmt::Display {
-    let dots = parse(file_content).collect_vec();
-    let external_and_internal = find_exterior_area(dots.iter().cloned());
-    let internal_dots = find_internal_dots(&dots);
-    let external_area_of_internal = find_exterior_area(internal_dots.into_iter());
-    external_and_internal - external_area_of_internal
-}
-
-fn parse(file_content: &str) -> impl Iterator<Item = (i32, i32, i32)> + '_ {
-    file_content
-        .lines()
-        .map(|line| {
-            line.split(",")
-                .map(|x| -> i32 { x.parse().unwrap() })
-                .collect::<Vec<_>>()
-        })
-        .map(|v| (v[0], v[1], v[2]))
-}
-#[cfg(test)]
-mod tests {
-    use super::*;
-    const INPUT: &str = include_str!("./y22d18/example.txt");
-    const ACTUAL: &str = include_str!("../benches/y22d18.txt");
-    #[test]
-    #[ignore]
-    fn test_task1() {
-        assert_eq!(format!("{}", solve_task1(INPUT)), "64");
+    fn title(&self) -> &'static str {
+        "Boiling Boulders"
     }
-
-    #[test]
-    #[ignore]
-    fn test_task1_actual() {
-        assert_eq!(format!("{}", solve_task1(ACTUAL)), "3432");
+    fn part1(&self, file_content: &str, _params: &Params) -> Result<Answer, ParseError> {
+        Ok(solve_task1(file_content)?.into())
     }
-
-    #[test]
-    #[ignore]
-    fn test_task2() {
-        assert_eq!(format!("{}", solve_task2(INPUT)), "58");
+    fn part2(&self, file_content: &str, _params: &Params) -> Result<Answer, ParseError> {
+        Ok(solve_task2(file_content)?.into())
     }
+}
 
-    #[test]
-    #[ignore]
-    fn test_task2_actual() {
-        assert_eq!(format!("{}", solve_task2(ACTUAL)), "2042");
-    }
+pub fn register(registry: &mut Registry) {
+    registry.register(&Day);
 }
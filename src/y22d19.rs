@@ -1,10 +1,13 @@
+use crate::{
+    parsing::{parse_lines, tag, IResult, ParseError},
+    registry::Registry,
+    solution::{Answer, Cancel, Parameter, Params, Solution},
+    trace::Trace,
+};
 use nom::{
-    bytes::complete::tag,
     character::{self},
     sequence::preceded,
-    IResult,
 };
-use rayon::prelude::*;
 use std::collections::HashMap;
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
@@ -15,7 +18,7 @@ enum Resource {
     Geode,
 }
 
-#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Default)]
 struct History(u64, u32);
 
 impl History {
@@ -51,13 +54,7 @@ impl History {
     }
 }
 
-impl Default for History {
-    fn default() -> Self {
-        Self(0, 0)
-    }
-}
-
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, Default)]
 struct Resources {
     ore: usize,
     clay: usize,
@@ -77,7 +74,7 @@ impl Resources {
             ore: self.ore - other.ore,
             clay: self.clay - other.clay,
             obsidian: self.obsidian - other.obsidian,
-            geode: self.obsidian - other.obsidian,
+            geode: self.geode - other.geode,
         }
     }
     pub fn add(&self, other: &Resources) -> Self {
@@ -146,24 +143,13 @@ impl Resources {
     }
 }
 
-impl Default for Resources {
-    fn default() -> Self {
-        Self {
-            ore: Default::default(),
-            clay: Default::default(),
-            obsidian: Default::default(),
-            geode: Default::default(),
-        }
-    }
-}
-
 #[derive(Debug)]
 struct Blueprint {
     id: usize,
     cost: HashMap<Resource, Resources>,
 }
 
-fn parse_blueprint(line: &str) -> IResult<&str, Blueprint> {
+fn parse_blueprint(line: &str) -> IResult<'_, Blueprint> {
     let (input, blueprint_id) = preceded(tag("Blueprint "), character::complete::u32)(line)?;
     let (input, ore_robot_ore_cost) =
         preceded(tag(": Each ore robot costs "), character::complete::u32)(input)?;
@@ -183,6 +169,7 @@ fn parse_blueprint(line: &str) -> IResult<&str, Blueprint> {
     )(input)?;
     let (input, geode_robot_obsidian_cost) =
         preceded(tag(" ore and "), character::complete::u32)(input)?;
+    let (input, _) = tag(" obsidian.")(input)?;
     let mut costs = HashMap::new();
     costs.insert(
         Resource::Ore,
@@ -227,11 +214,16 @@ fn parse_blueprint(line: &str) -> IResult<&str, Blueprint> {
     Ok((input, blueprint))
 }
 
-fn get_quality_level(blueprint: &Blueprint) -> usize {
-    blueprint.id * get_max_geodes(&blueprint)
+fn get_quality_level(
+    blueprint: &Blueprint,
+    minutes: usize,
+    cancel: &Cancel,
+    trace: &Trace,
+) -> usize {
+    blueprint.id * get_max_geodes(blueprint, minutes, cancel, trace)
 }
 
-#[derive(Clone)]
+#[derive(Clone, Default)]
 struct State {
     resources: Resources,
     history: History,
@@ -240,51 +232,40 @@ struct State {
 }
 
 impl State {
-    pub fn print(&self, blueprint: &Blueprint) {
+    /// Emits the minutes that led to the state.
+    pub fn trace(&self, blueprint: &Blueprint, trace: &Trace) {
         let mut previous_states: Vec<State> =
             std::iter::successors(Some(self.clone()), |next_state| {
-                get_previous_state(&blueprint, next_state)
+                get_previous_state(blueprint, next_state)
             })
             .collect::<Vec<_>>();
         previous_states.reverse();
         for s in previous_states {
-            println!("Minute {}", s.elapsed_minutes + 1);
-            let resource = self.history.get(s.elapsed_minutes);
-            match &resource {
+            let minute = s.elapsed_minutes + 1;
+            let resources = format!(
+                "{} ore, {} clay, {} obsidian, {} geode",
+                s.resources.ore, s.resources.clay, s.resources.obsidian, s.resources.geode
+            );
+            match self.history.get(s.elapsed_minutes) {
                 Some(r) => {
-                    match r {
-                        Resource::Ore => println!("Build ore robot"),
-                        Resource::Clay => println!("Build clay robot"),
-                        Resource::Obsidian => println!("Build obsidian robot"),
-                        Resource::Geode => println!("Build geode robot"),
-                    }
-                    let cost = blueprint.cost.get(r).unwrap();
-                    println!("Spent {:?}", cost);
+                    let cost = blueprint.cost.get(&r).unwrap();
+                    trace.event(
+                        "minute",
+                        &[
+                            ("minute", &minute),
+                            ("build", &format!("{r:?}")),
+                            ("spent", &format!("{cost:?}")),
+                            ("resources", &resources),
+                        ],
+                    );
                 }
-                None => {}
+                None => trace.event("minute", &[("minute", &minute), ("resources", &resources)]),
             }
-            println!(
-                "Resources: {} ore, {} clay, {} obsidian, {} geode",
-                s.resources.ore, s.resources.clay, s.resources.obsidian, s.resources.geode
-            );
-            println!();
         }
     }
 }
 
-impl Default for State {
-    fn default() -> Self {
-        Self {
-            resources: Default::default(),
-            history: Default::default(),
-            robots: Default::default(),
-            elapsed_minutes: Default::default(),
-        }
-    }
-}
-
-const MAX_MINUTES: usize = 24;
-fn get_max_geodes(blueprint: &Blueprint) -> usize {
+fn get_max_geodes(blueprint: &Blueprint, minutes: usize, cancel: &Cancel, trace: &Trace) -> usize {
     let mut states = Vec::new();
     states.push(State {
         resources: Resources::default(),
@@ -298,9 +279,12 @@ fn get_max_geodes(blueprint: &Blueprint) -> usize {
 
     let mut max_geodes = 0;
     while let Some(state) = states.pop() {
-        if state.elapsed_minutes >= MAX_MINUTES {
+        cancel.check();
+        if state.elapsed_minutes >= minutes {
             if state.resources.geode > max_geodes {
-                state.print(&blueprint);
+                if trace.is_enabled() {
+                    state.trace(blueprint, trace);
+                }
                 max_geodes = state.resources.geode
             }
             continue;
@@ -331,7 +315,7 @@ fn get_max_geodes(blueprint: &Blueprint) -> usize {
             {
                 continue;
             }
-            if state.resources.is_enough_for(&cost) {
+            if state.resources.is_enough_for(cost) {
                 states.push(build_robot(blueprint, resource, &state));
             }
         }
@@ -340,7 +324,7 @@ fn get_max_geodes(blueprint: &Blueprint) -> usize {
 }
 
 fn get_previous_state(blueprint: &Blueprint, state: &State) -> Option<State> {
-    if state.elapsed_minutes <= 0 {
+    if state.elapsed_minutes == 0 {
         return None;
     }
     let prev_minutes = state.elapsed_minutes - 1;
@@ -374,48 +358,60 @@ fn build_robot(blueprint: &Blueprint, new_robot_resource: Resource, state: &Stat
     let new_robots = state.robots.add_of(new_robot_resource, 1);
     let new_history = state.history.set(state.elapsed_minutes, new_robot_resource);
     let new_elapsed_minutes = state.elapsed_minutes + 1;
-    let new_state = State {
+
+    State {
         resources: new_resources,
         robots: new_robots,
         history: new_history,
         elapsed_minutes: new_elapsed_minutes,
-    };
-    new_state
+    }
 }
 
-pub fn solve_task1(file_content: &str) -> usize {
-    file_content
-        .lines()
-        .map(|line| parse_blueprint(line).unwrap().1)
-        .map(|b| get_quality_level(&b))
+pub fn solve_task1(
+    file_content: &str,
+    minutes: usize,
+    cancel: &Cancel,
+    trace: &Trace,
+) -> Result<usize, ParseError> {
+    parse_lines(file_content, parse_blueprint)
+        .map(|b| b.map(|b| get_quality_level(&b, minutes, cancel, trace)))
         .sum()
 }
-pub fn solve_task2(file_content: &str) -> impl std::fmt::Display {
+pub fn solve_task2(_file_content: &str) -> usize {
     0
 }
-#[cfg(test)]
-mod tests {
-    use super::*;
-    const INPUT: &str = include_str!("./y22d19/example.txt");
-    const ACTUAL: &str = include_str!("../benches/y22d19.txt");
 
-    #[test]
-    fn test_task1() {
-        assert_eq!(format!("{}", solve_task1(INPUT)), "33");
-    }
+const PARAMETERS: &[Parameter] = &[Parameter::new::<usize>("minutes", "24")];
 
-    #[test]
-    fn test_task1_actual() {
-        assert_eq!(format!("{}", solve_task1(ACTUAL)), "0");
-    }
+pub struct Day;
 
-    #[test]
-    fn test_task2() {
-        assert_eq!(format!("{}", solve_task2(INPUT)), "0");
+impl Solution for Day {
+    fn year(&self) -> u16 {
+        2022
     }
-
-    #[test]
-    fn test_task2_actual() {
-        assert_eq!(format!("{}", solve_task2(ACTUAL)), "0");
+    fn day(&self) -> u8 {
+        19
+    }
+    fn title(&self) -> &'static str {
+        "Not Enough Minerals"
+    }
+    fn part1(&self, file_content: &str, params: &Params) -> Result<Answer, ParseError> {
+        Ok(solve_task1(
+            file_content,
+            params.get("minutes"),
+            params.cancel(),
+            params.trace(),
+        )?
+        .into())
+    }
+    fn part2(&self, file_content: &str, _params: &Params) -> Result<Answer, ParseError> {
+        Ok(solve_task2(file_content).into())
     }
+    fn parameters(&self) -> &'static [Parameter] {
+        PARAMETERS
+    }
+}
+
+pub fn register(registry: &mut Registry) {
+    registry.register(&Day);
 }